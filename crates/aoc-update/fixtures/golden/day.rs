@@ -0,0 +1,47 @@
+use common::{Problem, Solution};
+/// \--- Day 6: Wait For It ---
+/// ----------
+///
+/// The ferry quickly brings you across Island Island. As you try to figure out what to do next, you notice a poster on a wall near the ferry dock. "Boat races! Open to the public! Grand prize is an all-expenses-paid trip to *Desert Island*!"
+///
+/// For example:
+///
+/// ```
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+///
+/// This document describes three races:
+///
+/// * The first race lasts 7 milliseconds.
+/// * The second race lasts 15 milliseconds.
+///
+/// To see how much margin of error you have, determine the *number of ways you can beat the record* in each race; in this example, you get `*288*` (`4` \* `8` \* `9`).
+///
+/// \--- Part Two ---
+/// ----------
+///
+/// As the race is about to start, you realize the piece of paper actually just has very bad [kerning](https://en.wikipedia.org/wiki/Kerning).
+pub struct Day06;
+impl Problem for Day06 {
+    fn problem_input(&self) -> &'static str {
+        common::problem_input!(2023, day06)
+    }
+    fn day(&self) -> u8 {
+        6
+    }
+    fn name(&self) -> &str {
+        "Day 6: Wait For It"
+    }
+    fn solve_part1_with(&self, input: &str) -> Solution {
+        Solution::Todo
+    }
+    fn solve_part2_with(&self, input: &str) -> Solution {
+        Solution::Todo
+    }
+}
+common::aoc_test! {
+    Day06 {};
+    part1: Solution::Todo;
+    part2: Solution::Todo;
+}
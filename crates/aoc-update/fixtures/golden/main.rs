@@ -0,0 +1,105 @@
+#![feature(const_for)]
+use mimalloc::MiMalloc;
+#[global_allocator]
+static GLOBAL: MiMalloc = MiMalloc;
+use common::{BenchmarkCollection, Problem};
+mod day06;
+mod day06fast;
+mod day10;
+use day06::*;
+use day06fast::*;
+use day10::*;
+fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    if let Some(dir) = args
+        .iter()
+        .position(|a| a == "--inputs")
+        .and_then(|idx| args.get(idx + 1))
+    {
+        common::input::set_inputs_dir(dir);
+    }
+    let problems: Vec<Box<dyn Problem>> = vec![
+        Box::new(Day06), Box::new(Day06Fast), Box::new(Day10)
+    ];
+    if args.contains(&"bench".to_string()) && args.contains(&"--scaling".to_string()) {
+        common::scaling::scale_all(&problems);
+    } else if args.contains(&"bench".to_string()) {
+        for problem in problems {
+            let bench = problem.bench_part1();
+            println!(
+                "{} - Part 1: {:?} ({} runs)", problem.name(), bench.average(), bench
+                .results.len()
+            );
+            let bench = problem.bench_part2();
+            println!(
+                "{} - Part 2: {:?} ({} runs)", problem.name(), bench.average(), bench
+                .results.len()
+            );
+        }
+    } else if args.contains(&"bench-md".to_string()) {
+        let mut collection = BenchmarkCollection::new(
+            format!("Advent of Code {}", 2023),
+        );
+        for problem in problems {
+            let bench = problem.bench_part1();
+            collection.add(bench);
+            let bench = problem.bench_part2();
+            collection.add(bench);
+        }
+        println!("{}", collection.to_markdown());
+    } else if args.contains(&"progress".to_string()) {
+        let progress = common::progress::YearProgress::collect(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        print!("{}", progress.calendar());
+    } else if args.contains(&"progress-md".to_string()) {
+        let progress = common::progress::YearProgress::collect(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        let readme = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+        common::progress::write_readme(std::path::Path::new(readme), &progress)
+            .expect("Failed to write README");
+    } else if args.contains(&"all-inputs".to_string()) {
+        let failures = common::input_sets::check_all(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"diff".to_string()) {
+        let failures = common::differential::check_all(&problems);
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"reference".to_string()) {
+        let failures = common::differential::check_references(&problems);
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"examples".to_string()) {
+        let day = args
+            .iter()
+            .skip_while(|a| *a != "examples")
+            .nth(1)
+            .and_then(|d| d.parse().ok());
+        let failures = common::examples::check_all(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+            day,
+        );
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else {
+        for problem in problems {
+            println!("{} - Part 1: {}", problem.name(), problem.solve_part1());
+            println!("{} - Part 2: {}", problem.name(), problem.solve_part2());
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use common::{Problem, Solution};
+/// \--- Day 6: Wait For It ---
+/// ----------
+///
+/// The ferry quickly brings you across Island Island. As you try to figure out what to do next, you notice a poster on a wall near the ferry dock. "Boat races! Open to the public! Grand prize is an all-expenses-paid trip to *Desert Island*!"
+///
+/// For example:
+///
+/// ```
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+///
+/// This document describes three races:
+///
+/// * The first race lasts 7 milliseconds.
+/// * The second race lasts 15 milliseconds.
+///
+/// To see how much margin of error you have, determine the *number of ways you can beat the record* in each race; in this example, you get `*288*` (`4` \* `8` \* `9`).
+///
+/// \--- Part Two ---
+/// ----------
+///
+/// As the race is about to start, you realize the piece of paper actually just has very bad [kerning](https://en.wikipedia.org/wiki/Kerning).
+pub struct Day06;
+/// One race from the sheet of paper.
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+/// Helpers whose names start with `Day` keep their docs too.
+struct DayPlan {
+    races: Vec<Race>,
+}
+impl Race {
+    fn winning_options(&self) -> u64 {
+        (0..=self.time).filter(|hold| hold * (self.time - hold) > self.distance).count()
+            as u64
+    }
+}
+impl Day06 {
+    fn parse(&self, input: &str) -> DayPlan {
+        let mut lines = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .skip(1)
+                    .map(|n| n.parse::<u64>().unwrap())
+                    .collect::<Vec<_>>()
+            });
+        let (times, distances) = (lines.next().unwrap(), lines.next().unwrap());
+        DayPlan {
+            races: times
+                .into_iter()
+                .zip(distances)
+                .map(|(time, distance)| Race { time, distance })
+                .collect(),
+        }
+    }
+}
+const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+impl Problem for Day06 {
+    fn problem_input(&self) -> &'static str {
+        common::problem_input!(2023, day06)
+    }
+    fn day(&self) -> u8 {
+        6u8
+    }
+    fn name(&self) -> &str {
+        "Day 6: Wait For It"
+    }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+    fn solve_part1_with(&self, input: &str) -> Solution {
+        Solution::U64(
+            self.parse(input).races.iter().map(|r| r.winning_options()).product(),
+        )
+    }
+    fn solve_part2_with(&self, _input: &str) -> Solution {
+        Solution::Todo
+    }
+}
+common::aoc_test! {
+    Day06 {};
+    part1: Solution::U64(449820), examples: [EXAMPLE => Solution::U64(288)];
+    part2: Solution::Todo;
+    variants: [crate::day06fast::Day06Fast {}];
+
+    #[test]
+    fn test_single_race() {
+        let problem = Day06 {};
+        assert_eq!(problem.solve_part1_with("Time: 7\nDistance: 9"), Solution::U64(4));
+    }
+}
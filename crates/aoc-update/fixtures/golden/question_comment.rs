@@ -0,0 +1,24 @@
+/// \--- Day 6: Wait For It ---
+/// ----------
+///
+/// The ferry quickly brings you across Island Island. As you try to figure out what to do next, you notice a poster on a wall near the ferry dock. "Boat races! Open to the public! Grand prize is an all-expenses-paid trip to *Desert Island*!"
+///
+/// For example:
+///
+/// ```
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+///
+/// This document describes three races:
+///
+/// * The first race lasts 7 milliseconds.
+/// * The second race lasts 15 milliseconds.
+///
+/// To see how much margin of error you have, determine the *number of ways you can beat the record* in each race; in this example, you get `*288*` (`4` \* `8` \* `9`).
+///
+/// \--- Part Two ---
+/// ----------
+///
+/// As the race is about to start, you realize the piece of paper actually just has very bad [kerning](https://en.wikipedia.org/wiki/Kerning).
+pub struct Day06;
@@ -0,0 +1,78 @@
+use common::{Problem, Solution};
+/// An outdated description.
+/// ----------
+pub struct Day06Fast;
+/// One race from the sheet of paper.
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+/// Helpers whose names start with `Day` keep their docs too.
+struct DayPlan {
+    races: Vec<Race>,
+}
+impl Race {
+    fn winning_options(&self) -> u64 {
+        (0..=self.time).filter(|hold| hold * (self.time - hold) > self.distance).count()
+            as u64
+    }
+}
+impl Day06Fast {
+    fn parse(&self, input: &str) -> DayPlan {
+        let mut lines = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .skip(1)
+                    .map(|n| n.parse::<u64>().unwrap())
+                    .collect::<Vec<_>>()
+            });
+        let (times, distances) = (lines.next().unwrap(), lines.next().unwrap());
+        DayPlan {
+            races: times
+                .into_iter()
+                .zip(distances)
+                .map(|(time, distance)| Race { time, distance })
+                .collect(),
+        }
+    }
+}
+const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+impl Problem for Day06Fast {
+    fn problem_input(&self) -> &'static str {
+        common::problem_input!(2023, day06)
+    }
+    fn day(&self) -> u8 {
+        6u8
+    }
+    fn name(&self) -> &str {
+        "Day 6: Wait For It (Fast)"
+    }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+    fn solve_part1_with(&self, input: &str) -> Solution {
+        Solution::U64(
+            self.parse(input).races.iter().map(|r| r.winning_options()).product(),
+        )
+    }
+    fn solve_part2_with(&self, _input: &str) -> Solution {
+        Solution::Todo
+    }
+    fn variant(&self) -> Option<&str> {
+        Some("Fast")
+    }
+}
+common::aoc_test! {
+    Day06Fast {};
+    part1: Solution::U64(449820), examples: [EXAMPLE => Solution::U64(288)];
+    part2: Solution::Todo;
+
+    #[test]
+    fn test_single_race() {
+        let problem = Day06Fast {};
+        assert_eq!(problem.solve_part1_with("Time: 7\nDistance: 9"), Solution::U64(4));
+    }
+}
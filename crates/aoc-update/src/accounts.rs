@@ -0,0 +1,271 @@
+use std::{
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use common::input_sets::DEFAULT_ACCOUNT;
+use config_better::Config;
+use tokio::io::AsyncWriteExt;
+use tracing::*;
+
+use crate::http::{HttpConfig, PoliteClient};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TokenError {
+    #[error(
+        "The session token for {0} has expired or is invalid. Log in to adventofcode.com, copy \
+         the `session` cookie and run `aoc-update login {0}`"
+    )]
+    Expired(String),
+    #[error("No session token for {0}, add one with `aoc-update login {0}`")]
+    Missing(String),
+    #[error("Account names can only contain letters, digits, `_` and `-`, not {0:?}")]
+    InvalidName(String),
+}
+
+/// A page the site doesn't have, which is what a puzzle looks like until it
+/// unlocks.
+#[derive(Debug, thiserror::Error)]
+#[error("{0} returned 404 Not Found")]
+pub struct NotFound(pub String);
+
+/// A named Advent of Code session token. Every account gets its own inputs.
+pub struct Account {
+    pub name: String,
+    pub token: String,
+}
+
+impl Account {
+    pub fn client(&self, config: &HttpConfig) -> anyhow::Result<PoliteClient> {
+        let token_cookie = format!("session={}; Domain=adventofcode.com", self.token);
+        let cookie_jar = Arc::new(reqwest::cookie::Jar::default());
+        cookie_jar
+            .add_cookie_str(&token_cookie, &"https://adventofcode.com".parse::<reqwest::Url>()?);
+        Ok(PoliteClient::new(
+            reqwest::ClientBuilder::new().cookie_provider(cookie_jar),
+            config.clone(),
+        )?)
+    }
+
+    /// Fetches the settings page to find out who the token belongs to.
+    pub async fn whoami(&self, config: &HttpConfig) -> anyhow::Result<String> {
+        let page = fetch_page(&self.client(config)?, "https://adventofcode.com/settings")
+            .await
+            .map_err(|e| self.expired_or(e))?;
+        let fragment = scraper::Html::parse_fragment(&page);
+        let selector =
+            scraper::Selector::parse("div.user").map_err(|_| anyhow::anyhow!("Bad selector"))?;
+        fragment
+            .select(&selector)
+            .next()
+            .and_then(|user| user.text().next())
+            .map(|name| name.trim().to_owned())
+            .ok_or_else(|| TokenError::Expired(self.name.clone()).into())
+    }
+
+    /// Names this account in an expired token error, leaving others alone.
+    pub fn expired_or(&self, error: anyhow::Error) -> anyhow::Error {
+        match error.downcast_ref::<TokenError>() {
+            Some(TokenError::Expired(_)) => TokenError::Expired(self.name.clone()).into(),
+            _ => error,
+        }
+    }
+}
+
+/// Fetches a page, failing with [`TokenError::Expired`] if the site didn't
+/// accept the session token.
+pub async fn fetch_page(client: &PoliteClient, url: &str) -> anyhow::Result<String> {
+    let response = client.get(url).await?;
+    let status = response.status();
+    let page = response.text().await?;
+
+    // Inputs are refused outright, while other pages render logged out with a
+    // link to log in.
+    if status == reqwest::StatusCode::BAD_REQUEST && page.contains("Please log in")
+        || page.contains("href=\"/auth/login\"")
+    {
+        return Err(TokenError::Expired(DEFAULT_ACCOUNT.to_owned()).into());
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(NotFound(url.to_owned()).into());
+    }
+    if !status.is_success() {
+        anyhow::bail!("{} returned {}: {}", url, status, page.trim());
+    }
+    Ok(page)
+}
+
+/// Writes a file only the current user can read.
+pub async fn write_private(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    // The mode only applies to new files, so tighten an existing one before
+    // anything is written to it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600)).await?;
+    }
+    file.write_all(contents.as_bytes()).await?;
+    // Tokio writes in the background, so wait for it before the file is read.
+    file.flush().await?;
+    Ok(())
+}
+
+fn config_dir() -> PathBuf {
+    Config::new("aoc-update").config.path
+}
+
+/// Names become file names, so they're kept to characters that can't escape
+/// the tokens directory.
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// The default account keeps using `token.txt`, every other account is stored
+/// in `tokens/<name>.txt`.
+pub fn token_path(name: &str) -> Result<PathBuf, TokenError> {
+    if !valid_name(name) {
+        Err(TokenError::InvalidName(name.to_owned()))
+    } else if name == DEFAULT_ACCOUNT {
+        Ok(config_dir().join("token.txt"))
+    } else {
+        Ok(config_dir().join("tokens").join(format!("{}.txt", name)))
+    }
+}
+
+async fn read_account(name: String) -> Option<Account> {
+    let token = tokio::fs::read_to_string(token_path(&name).ok()?).await.ok()?;
+    Some(Account { name, token: token.trim().to_owned() })
+}
+
+/// Loads one account by name.
+pub async fn load_account(name: &str) -> anyhow::Result<Account> {
+    token_path(name)?;
+    Ok(read_account(name.to_owned()).await.ok_or_else(|| TokenError::Missing(name.to_owned()))?)
+}
+
+/// Checks a token works, then stores it for `name`.
+async fn save_account(account: &Account, config: &HttpConfig) -> anyhow::Result<()> {
+    let path = token_path(&account.name)?;
+    let user = account.whoami(config).await?;
+    write_private(&path, &account.token).await?;
+
+    info!("Logged in {} as {}, token saved to {:?}", account.name, user, path);
+    Ok(())
+}
+
+/// Loads the default account, if there is one, followed by the others sorted
+/// by name.
+pub async fn load_accounts() -> anyhow::Result<Vec<Account>> {
+    let mut names = vec![];
+    if let Ok(mut entries) = tokio::fs::read_dir(config_dir().join("tokens")).await {
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                if let Some(name) = path.file_stem() {
+                    names.push(name.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+
+    let mut accounts = vec![];
+    for name in std::iter::once(DEFAULT_ACCOUNT.to_owned()).chain(names) {
+        if let Some(account) = read_account(name).await {
+            accounts.push(account);
+        }
+    }
+
+    if accounts.is_empty() {
+        return Err(TokenError::Missing(DEFAULT_ACCOUNT.to_owned()).into());
+    }
+    Ok(accounts)
+}
+
+/// Reads a token from stdin, checks it works, then stores it for `name`.
+pub async fn login(name: &str, config: &HttpConfig) -> anyhow::Result<()> {
+    token_path(name)?;
+    eprint!("Paste the `session` cookie for {}: ", name);
+    std::io::stderr().flush()?;
+    let mut token = String::new();
+    std::io::stdin().lock().read_line(&mut token)?;
+
+    save_account(&Account { name: name.to_owned(), token: token.trim().to_owned() }, config).await
+}
+
+/// Prints who each stored token (or just `name`'s) belongs to.
+pub async fn whoami(name: Option<&str>, config: &HttpConfig) -> anyhow::Result<()> {
+    let accounts = match name {
+        Some(name) => vec![load_account(name).await?],
+        None => load_accounts().await?,
+    };
+    for account in accounts {
+        match account.whoami(config).await {
+            Ok(user) => println!("{}: {}", account.name, user),
+            Err(e) => println!("{}: {}", account.name, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles `aoc-update account <add|remove|list>`.
+pub async fn run(args: &[String], config: &HttpConfig) -> anyhow::Result<()> {
+    match args {
+        [command, name, token] if command == "add" => {
+            let account = Account { name: name.to_owned(), token: token.trim().to_owned() };
+            save_account(&account, config).await?;
+        }
+        [command, name] if command == "remove" => {
+            tokio::fs::remove_file(token_path(name)?).await?;
+            info!("Removed token for {}", name);
+        }
+        [command] if command == "list" => {
+            for account in load_accounts().await? {
+                println!("{}", account.name);
+            }
+        }
+        _ => error!("Usage: aoc-update account add <name> <token> | remove <name> | list"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_path() {
+        assert!(token_path("alt-2_b").unwrap().ends_with("tokens/alt-2_b.txt"));
+        assert!(token_path(DEFAULT_ACCOUNT).unwrap().ends_with("token.txt"));
+        for name in ["", "../default", "a/b", "..", "a.b", "tab\t"] {
+            assert!(matches!(token_path(name), Err(TokenError::InvalidName(_))), "{:?}", name);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("aoc-update-private-{}", std::process::id()));
+        std::fs::write(&path, "old token that was readable").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_private(&path, "token").await.unwrap();
+
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "token");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
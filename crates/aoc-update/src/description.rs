@@ -0,0 +1,247 @@
+use scraper::{ElementRef, Node};
+
+const SITE: &str = "https://adventofcode.com";
+
+/// Characters that would otherwise be read as Markdown.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Text with whitespace collapsed the way a browser would show it.
+fn push_text(out: &mut String, text: &str) {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let starts_with_space = text.starts_with(char::is_whitespace);
+    let ends_with_space = text.ends_with(char::is_whitespace) && !collapsed.is_empty();
+    if starts_with_space && !out.is_empty() && !out.ends_with(' ') {
+        out.push(' ');
+    }
+    out.push_str(&escape(&collapsed));
+    if ends_with_space {
+        out.push(' ');
+    }
+}
+
+fn code_span(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {} ``", code)
+    } else {
+        format!("`{}`", code)
+    }
+}
+
+/// `<code>` is often wrapped around, or wraps, a single `<em>` to highlight an
+/// answer. Emphasis can't go inside a code span, so it goes around it.
+fn code(element: ElementRef) -> String {
+    let text = element.text().collect::<String>();
+    let only_em = element.children().all(|child| match child.value() {
+        Node::Element(e) => e.name() == "em",
+        Node::Text(t) => t.trim().is_empty(),
+        _ => true,
+    });
+    let has_em =
+        element.children().any(|c| matches!(c.value(), Node::Element(e) if e.name() == "em"));
+    if only_em && has_em {
+        format!("*{}*", code_span(text.trim()))
+    } else {
+        code_span(&text)
+    }
+}
+
+/// Wraps `inner` in `marker`, keeping any surrounding spaces outside it so the
+/// emphasis still applies.
+fn wrap(out: &mut String, inner: &str, marker: &str) {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        out.push_str(inner);
+        return;
+    }
+    if inner.starts_with(' ') && !out.ends_with(' ') {
+        out.push(' ');
+    }
+    out.push_str(&format!("{}{}{}", marker, trimmed, marker));
+    if inner.ends_with(' ') {
+        out.push(' ');
+    }
+}
+
+fn absolute(href: &str) -> String {
+    match href.strip_prefix('/') {
+        Some(path) => format!("{}/{}", SITE, path),
+        None => href.to_owned(),
+    }
+}
+
+/// Converts an element's inline content. Nested lists are left to `list`.
+fn inline(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        let Some(child_element) = ElementRef::wrap(child) else {
+            if let Node::Text(text) = child.value() {
+                push_text(out, text);
+            }
+            continue;
+        };
+        let value = child_element.value();
+        match value.name() {
+            "ul" | "ol" => {}
+            "em" | "strong" => {
+                let mut inner = String::new();
+                inline(child_element, &mut inner);
+                wrap(out, &inner, "*");
+            }
+            "code" => {
+                // Code inside an `<em>` is already wrapped by the `<em>`.
+                let em = element.value().name() == "em";
+                let span = code(child_element);
+                out.push_str(if em { span.trim_matches('*') } else { &span });
+            }
+            "a" => {
+                let mut inner = String::new();
+                inline(child_element, &mut inner);
+                match value.attr("href") {
+                    Some(href) => out.push_str(&format!("[{}]({})", inner.trim(), absolute(href))),
+                    None => out.push_str(&inner),
+                }
+            }
+            "span" => {
+                let mut inner = String::new();
+                inline(child_element, &mut inner);
+                // Rustdoc passes inline HTML through, which keeps the hover text.
+                match value.attr("title") {
+                    Some(title) => out.push_str(&format!(
+                        "<span title=\"{}\">{}</span>",
+                        title.replace('&', "&amp;").replace('"', "&quot;"),
+                        inner
+                    )),
+                    None => out.push_str(&inner),
+                }
+            }
+            "br" => out.push('\n'),
+            _ => inline(child_element, out),
+        }
+    }
+}
+
+fn list(element: ElementRef, depth: usize) -> String {
+    let ordered = element.value().name() == "ol";
+    let mut lines = vec![];
+    for (idx, item) in element.children().filter_map(ElementRef::wrap).enumerate() {
+        let marker = if ordered { format!("{}. ", idx + 1) } else { "* ".to_owned() };
+        let mut text = String::new();
+        inline(item, &mut text);
+        lines.push(format!("{}{}{}", "  ".repeat(depth), marker, text.trim()));
+        for nested in item.children().filter_map(ElementRef::wrap) {
+            if matches!(nested.value().name(), "ul" | "ol") {
+                lines.push(list(nested, depth + 1));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn blocks(element: ElementRef, out: &mut Vec<String>) {
+    for child in element.children() {
+        let Some(child_element) = ElementRef::wrap(child) else {
+            if let Node::Text(text) = child.value() {
+                let mut paragraph = String::new();
+                push_text(&mut paragraph, text);
+                if !paragraph.trim().is_empty() {
+                    out.push(paragraph.trim().to_owned());
+                }
+            }
+            continue;
+        };
+        match child_element.value().name() {
+            "h2" => {
+                let mut heading = String::new();
+                inline(child_element, &mut heading);
+                // A line of dashes on its own would be a horizontal rule.
+                let heading = heading.trim();
+                let heading = if heading.starts_with('-') {
+                    format!("\\{}", heading)
+                } else {
+                    heading.to_owned()
+                };
+                out.push(format!("{}\n----------", heading));
+            }
+            "p" => {
+                let mut paragraph = String::new();
+                inline(child_element, &mut paragraph);
+                out.push(paragraph.trim().to_owned());
+            }
+            // Fenced as text so rustdoc never tries to compile an example.
+            "pre" => {
+                let text = child_element.text().collect::<String>();
+                out.push(format!("```text\n{}\n```", text.trim_end_matches('\n')));
+            }
+            "ul" | "ol" => out.push(list(child_element, 0)),
+            _ => blocks(child_element, out),
+        }
+    }
+}
+
+/// Converts a puzzle description's `<article>` to Markdown for doc comments.
+/// Unlike a general-purpose converter, emphasis on answers, links and the
+/// hover text on `<span title>`s survive, and examples are fenced as `text`.
+pub fn to_markdown(article: ElementRef) -> String {
+    let mut out = vec![];
+    blocks(article, &mut out);
+    out.retain(|block| !block.is_empty());
+    out.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden::{assert_golden, fixture};
+
+    fn convert(html: &str) -> String {
+        let document = scraper::Html::parse_fragment(html);
+        let selector = scraper::Selector::parse("article").unwrap();
+        document.select(&selector).map(to_markdown).collect::<Vec<_>>().join("\n\n")
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let html = fixture("description/day06.html");
+        assert_golden("description.md", &convert(&html));
+    }
+
+    #[test]
+    fn test_inline() {
+        let convert = |html: &str| convert(&format!("<article><p>{}</p></article>", html));
+        assert_eq!(convert("you get <code><em>288</em></code>."), "you get *`288`*.");
+        assert_eq!(convert("<em><code>4</code></em> ways"), "*`4`* ways");
+        assert_eq!(convert("the <em>lowest</em>\n  location"), "the *lowest* location");
+        assert_eq!(convert("<code>a<em>b</em></code>"), "`ab`");
+        assert_eq!(convert("<code>`</code>"), "`` ` ``");
+        assert_eq!(convert("2 * 3 &lt; 7_0"), "2 \\* 3 \\< 7\\_0");
+        assert_eq!(
+            convert("<a href=\"/2023/day/6/input\">input</a>"),
+            "[input](https://adventofcode.com/2023/day/6/input)"
+        );
+        assert_eq!(
+            convert("<span title=\"Not &quot;that&quot; one\">Island</span>"),
+            "<span title=\"Not &quot;that&quot; one\">Island</span>"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let markdown = convert(
+            "<article><h2>--- Day 1: Trebuchet?! ---</h2><pre><code>1abc2\n<em>pqr</em>3stu8vwx\n</code></pre>\
+             <ul><li>One<ul><li>Nested</li></ul></li><li><code>Two</code></li></ul></article>",
+        );
+        assert_eq!(
+            markdown,
+            "\\--- Day 1: Trebuchet?! ---\n----------\n\n```text\n1abc2\npqr3stu8vwx\n```\n\n* \
+             One\n  * Nested\n* `Two`"
+        );
+    }
+}
@@ -6,10 +6,35 @@ fn log_level_default() -> String {
     "info".to_owned()
 }
 
+fn request_interval_ms_default() -> u64 {
+    3000
+}
+
+fn max_retries_default() -> u32 {
+    3
+}
+
+fn templates_dir_default() -> String {
+    "templates".to_owned()
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Environment {
     #[serde(default = "log_level_default")]
     pub log_level: String,
     #[serde(default)]
     pub log_format: LogFormat,
+    /// Email or URL included in the User-Agent so the site can reach out about
+    /// misbehaving traffic.
+    #[serde(default)]
+    pub aoc_contact: Option<String>,
+    /// Minimum time between two requests to the site, across runs.
+    #[serde(default = "request_interval_ms_default")]
+    pub aoc_request_interval_ms: u64,
+    #[serde(default = "max_retries_default")]
+    pub aoc_max_retries: u32,
+    /// Directory with templates overriding the built-in ones for generated
+    /// files.
+    #[serde(default = "templates_dir_default")]
+    pub aoc_templates_dir: String,
 }
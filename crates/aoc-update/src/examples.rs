@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use common::examples::{example_name, format_answers, parse_answers, ANSWERS_FILE};
+use tracing::*;
+
+/// The text of each `<pre><code>` block in one part's description. Not every
+/// block is an input, but it's easier to delete the ones that aren't than to
+/// copy the ones that are.
+pub fn code_blocks(article: scraper::ElementRef) -> anyhow::Result<Vec<String>> {
+    let selector =
+        scraper::Selector::parse("pre > code").map_err(|_| anyhow::anyhow!("Bad selector"))?;
+    Ok(article.select(&selector).map(|code| code.text().collect::<String>()).collect())
+}
+
+/// Writes each part's code blocks to `dir` as `part1_a.txt` and so on, and
+/// adds them to the answers file with a blank answer. Examples that are
+/// already there are left alone, so answers filled in by hand are kept and
+/// re-running after part 2 unlocks only adds part 2's.
+pub async fn write_examples(dir: &Path, parts: &[Vec<String>]) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    let answers_path = dir.join(ANSWERS_FILE);
+    let mut answers = match tokio::fs::read_to_string(&answers_path).await {
+        Ok(answers) => parse_answers(&answers),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e.into()),
+    };
+
+    for (part, blocks) in (1..).zip(parts) {
+        for (idx, block) in blocks.iter().enumerate() {
+            let name = example_name(part, idx);
+            let path = dir.join(format!("{}.txt", name));
+            if tokio::fs::try_exists(&path).await? {
+                continue;
+            }
+            tokio::fs::write(&path, block).await?;
+            info!("Saved example {}", name);
+            if !answers.iter().any(|(n, _)| *n == name) {
+                answers.push((name, None));
+            }
+        }
+    }
+
+    tokio::fs::write(answers_path, format_answers(&answers)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_blocks() {
+        let html = scraper::Html::parse_fragment(
+            "<article class=\"day-desc\"><p>For example:</p><pre><code>1abc2\n<em>pqr</em>3stu8vwx\n</code></pre>\
+             <p>Then <code>12</code>.</p><pre><code>treb7uchet\n</code></pre></article>",
+        );
+        let selector = scraper::Selector::parse("article").unwrap();
+        let article = html.select(&selector).next().unwrap();
+        assert_eq!(code_blocks(article).unwrap(), ["1abc2\npqr3stu8vwx\n", "treb7uchet\n"]);
+    }
+
+    #[tokio::test]
+    async fn test_write_examples() {
+        let dir = std::env::temp_dir().join(format!("aoc-update-examples-{}", std::process::id()));
+        write_examples(&dir, &[vec!["a\n".to_owned(), "b\n".to_owned()]]).await.unwrap();
+        // Fill in an answer by hand, then pick up part 2.
+        std::fs::write(dir.join(ANSWERS_FILE), "part1_a: 1\npart1_b:\n").unwrap();
+        std::fs::write(dir.join("part1_a.txt"), "edited\n").unwrap();
+        write_examples(&dir, &[vec!["a\n".to_owned()], vec!["c\n".to_owned()]]).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(dir.join("part1_a.txt")).unwrap(), "edited\n");
+        assert_eq!(std::fs::read_to_string(dir.join("part2_a.txt")).unwrap(), "c\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.join(ANSWERS_FILE)).unwrap(),
+            "part1_a: 1\npart1_b:\npart2_a:\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,423 @@
+use std::path::Path;
+
+use common::{
+    examples::EXAMPLES_DIR,
+    input::{inputs_dir, read_input, InputError},
+};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::ToTokens;
+use toml_edit::{value, ArrayOfTables, Document, Item, Table};
+use tracing::*;
+
+use crate::{crate_features, day_modules, inputs::INPUTS_DIR, pretty, src_dir};
+
+const FUZZ_DIR: &str = "fuzz";
+
+const FUZZ_MANIFEST: &str = r#"[package]
+name = "aoc-fuzz"
+version = "0.0.0"
+publish = false
+edition = "2021"
+
+[package.metadata]
+cargo-fuzz = true
+
+[dependencies]
+common = { path = "../crates/common" }
+libfuzzer-sys = "0.4.7"
+
+[features]
+embed-inputs = []
+embed-encrypted-inputs = []
+
+# Keep the fuzz targets out of the main workspace.
+[workspace]
+members = ["."]
+"#;
+
+const FUZZ_GITIGNORE: &str = "target\ncorpus\nartifacts\ncoverage\n";
+
+pub fn target_name(year: u32, module: &Ident) -> String {
+    format!("aoc{}_{}", year, module)
+}
+
+/// A libFuzzer target that feeds arbitrary text into both parts of a day.
+/// Year crates are binaries, so the day's module is pulled in by path, along
+/// with any `#![feature]`s the year enables.
+fn fuzz_target(year: u32, module: &Ident, problem: &Ident, features: &str) -> String {
+    format!(
+        r#"#![no_main]
+{features}
+use common::Problem;
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../crates/aoc{year}/src/{module}/mod.rs"]
+mod {module};
+
+fuzz_target!(|data: &[u8]| {{
+    // Puzzle inputs are always text.
+    if let Ok(input) = std::str::from_utf8(data) {{
+        let problem = {module}::{problem} {{}};
+        let _ = problem.solve_part1_with(input);
+        let _ = problem.solve_part2_with(input);
+    }}
+}});
+"#
+    )
+}
+
+/// Features `common::problem_input!` checks for in the crate it's expanded in,
+/// which for the fuzz targets is the fuzz crate.
+const INPUT_FEATURES: [&str; 2] = ["embed-inputs", "embed-encrypted-inputs"];
+
+/// Declares the input features in the fuzz manifest, so the day modules'
+/// `cfg`s are known there. Returns `None` if they're already declared.
+pub fn add_features(manifest: &str) -> anyhow::Result<Option<String>> {
+    let mut document = manifest.parse::<Document>()?;
+    let features = document
+        .entry("features")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("`features` in the fuzz manifest isn't a table"))?;
+
+    let mut changed = false;
+    for feature in INPUT_FEATURES {
+        if !features.contains_key(feature) {
+            features.insert(feature, value(toml_edit::Array::new()));
+            changed = true;
+        }
+    }
+
+    Ok(changed.then(|| document.to_string()))
+}
+
+/// Registers `name` as a `[[bin]]` in the fuzz manifest. Returns `None` if
+/// it's already there.
+pub fn add_fuzz_target(manifest: &str, name: &str) -> anyhow::Result<Option<String>> {
+    let mut document = manifest.parse::<Document>()?;
+    let bins = document
+        .entry("bin")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or_else(|| anyhow::anyhow!("`bin` in the fuzz manifest isn't a list of tables"))?;
+    if bins.iter().any(|bin| bin.get("name").and_then(|n| n.as_str()) == Some(name)) {
+        return Ok(None);
+    }
+
+    let mut bin = Table::new();
+    bin["name"] = value(name);
+    bin["path"] = value(format!("fuzz_targets/{}.rs", name));
+    bin["test"] = value(false);
+    bin["doc"] = value(false);
+    bin["bench"] = value(false);
+    bins.push(bin);
+    // Keep the list sorted, so regenerating doesn't depend on directory order.
+    let mut sorted = bins.iter().cloned().collect::<Vec<_>>();
+    sorted.sort_by_key(|bin| bin.get("name").and_then(|n| n.as_str()).map(|n| n.to_owned()));
+    bins.clear();
+    sorted.into_iter().for_each(|bin| bins.push(bin));
+
+    Ok(Some(document.to_string()))
+}
+
+/// Adds a year crate's dependencies to the fuzz manifest, since its days are
+/// compiled as part of the fuzz crate. Path dependencies are rebased onto the
+/// fuzz directory. Returns `None` if they're all there already.
+pub fn add_dependencies(manifest: &str, year_manifest: &str) -> anyhow::Result<Option<String>> {
+    let mut document = manifest.parse::<Document>()?;
+    let year_document = year_manifest.parse::<Document>()?;
+    let (Some(dependencies), Some(year_dependencies)) = (
+        document.get_mut("dependencies").and_then(|d| d.as_table_like_mut()),
+        year_document.get("dependencies").and_then(|d| d.as_table_like()),
+    ) else {
+        anyhow::bail!("Expected a [dependencies] table in both manifests");
+    };
+
+    let mut changed = false;
+    for (name, dependency) in year_dependencies.iter() {
+        if dependencies.contains_key(name) {
+            continue;
+        }
+        let mut dependency = dependency.clone();
+        if let Some(path) = dependency.get_mut("path") {
+            if let Some(relative) = path.as_str().and_then(|p| p.strip_prefix("../")) {
+                *path = value(format!("../crates/{}", relative));
+            }
+        }
+        dependencies.insert(name, dependency);
+        changed = true;
+    }
+    if changed {
+        dependencies.sort_values();
+    }
+
+    Ok(changed.then(|| document.to_string()))
+}
+
+fn string_literals(tokens: TokenStream, literals: &mut Vec<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Literal(literal) => {
+                if let Ok(s) = syn::parse2::<syn::LitStr>(literal.into_token_stream()) {
+                    literals.push(s.value());
+                }
+            }
+            TokenTree::Group(group) => string_literals(group.stream(), literals),
+            _ => {}
+        }
+    }
+}
+
+/// Inputs listed before `=>` in an `aoc_test!`'s `examples: [..]`, either as
+/// literals or as the name of a `&str` const.
+fn macro_examples(tokens: TokenStream, consts: &[(String, String)], examples: &mut Vec<String>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for window in tokens.windows(3) {
+        let [TokenTree::Ident(ident), TokenTree::Punct(colon), TokenTree::Group(group)] = window
+        else {
+            continue;
+        };
+        if ident != "examples" || colon.as_char() != ':' {
+            continue;
+        }
+        // Each entry starts the list or follows a top-level comma.
+        let mut at_input = true;
+        for token in group.stream() {
+            match token {
+                TokenTree::Punct(p) if p.as_char() == ',' => at_input = true,
+                TokenTree::Literal(literal) if at_input => {
+                    if let Ok(s) = syn::parse2::<syn::LitStr>(literal.into_token_stream()) {
+                        examples.push(s.value());
+                    }
+                    at_input = false;
+                }
+                TokenTree::Ident(ident) if at_input => {
+                    if let Some((_, value)) = consts.iter().find(|(name, _)| ident == name) {
+                        examples.push(value.clone());
+                    }
+                    at_input = false;
+                }
+                _ => at_input = false,
+            }
+        }
+    }
+}
+
+fn collect_examples(items: &[syn::Item], consts: &[(String, String)], examples: &mut Vec<String>) {
+    for item in items {
+        match item {
+            syn::Item::Fn(f) if f.sig.ident.to_string().contains("example") => {
+                string_literals(f.block.to_token_stream(), examples);
+            }
+            syn::Item::Macro(m) if pretty::is_aoc_test(m) => {
+                macro_examples(m.mac.tokens.clone(), consts, examples);
+            }
+            syn::Item::Mod(m) => {
+                if let Some((_, items)) = &m.content {
+                    collect_examples(items, consts, examples);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `&str` consts defined at the top level of a day.
+fn string_consts(file: &syn::File) -> Vec<(String, String)> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Const(c) => match c.expr.as_ref() {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => {
+                    Some((c.ident.to_string(), s.value()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// The examples from the puzzle description: those given to a day's
+/// `aoc_test!`, and string literals in any `*example*` tests.
+pub fn example_inputs(file: &syn::File) -> Vec<String> {
+    let mut examples = vec![];
+    collect_examples(&file.items, &string_consts(file), &mut examples);
+    let mut unique: Vec<String> = vec![];
+    for example in examples {
+        if !unique.contains(&example) {
+            unique.push(example);
+        }
+    }
+    unique
+}
+
+async fn seed_corpus(year: u32, module: &Ident, name: &str, source: &str) -> anyhow::Result<()> {
+    let corpus = Path::new(FUZZ_DIR).join("corpus").join(name);
+    tokio::fs::create_dir_all(&corpus).await?;
+
+    // Variants share the input of the day they're a variant of.
+    let day = module.to_string().chars().take(5).collect::<String>();
+    // Read the same way `problem_input!` does, so encrypted inputs are
+    // decrypted with the local key.
+    match read_input(&inputs_dir(INPUTS_DIR), year as u16, &day) {
+        Ok(input) => tokio::fs::write(corpus.join("input"), input).await?,
+        Err(InputError::Read(_, e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let mut examples = example_inputs(&syn::parse_file(source)?);
+    for example in common::examples::load(&src_dir(year).join(&day).join(EXAMPLES_DIR))? {
+        if !examples.contains(&example.input) {
+            examples.push(example.input);
+        }
+    }
+    for (idx, example) in examples.iter().enumerate() {
+        tokio::fs::write(corpus.join(format!("example_{}", idx + 1)), example).await?;
+    }
+
+    Ok(())
+}
+
+/// Writes a fuzz target for every day of a year, registers them with the fuzz
+/// crate and seeds their corpora from the inputs, the examples in tests and the
+/// examples directory.
+///
+/// Most solutions still `unwrap` when parsing, so malformed inputs are
+/// expected to crash until days return errors instead.
+pub async fn write_targets(year: u32) -> anyhow::Result<()> {
+    let fuzz = Path::new(FUZZ_DIR);
+    tokio::fs::create_dir_all(fuzz.join("fuzz_targets")).await?;
+    let manifest_path = fuzz.join("Cargo.toml");
+    let mut manifest = match tokio::fs::read_to_string(&manifest_path).await {
+        Ok(manifest) => manifest,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => FUZZ_MANIFEST.to_owned(),
+        Err(e) => return Err(e.into()),
+    };
+    if !tokio::fs::try_exists(fuzz.join(".gitignore")).await? {
+        tokio::fs::write(fuzz.join(".gitignore"), FUZZ_GITIGNORE).await?;
+    }
+
+    let src = src_dir(year);
+    let main = tokio::fs::read_to_string(src.join("main.rs")).await?;
+    let features = crate_features(&main)?;
+    let year_manifest = tokio::fs::read_to_string(format!("crates/aoc{}/Cargo.toml", year)).await?;
+    if let Some(updated) = add_dependencies(&manifest, &year_manifest)? {
+        manifest = updated;
+    }
+    if let Some(updated) = add_features(&manifest)? {
+        manifest = updated;
+    }
+
+    for (module, problem) in day_modules(&src).await? {
+        let name = target_name(year, &module);
+        let target = fuzz_target(year, &module, &problem, &features);
+        tokio::fs::write(fuzz.join("fuzz_targets").join(format!("{}.rs", name)), target).await?;
+        if let Some(updated) = add_fuzz_target(&manifest, &name)? {
+            manifest = updated;
+            info!("Added fuzz target {}", name);
+        }
+
+        let source = tokio::fs::read_to_string(src.join(module.to_string()).join("mod.rs")).await?;
+        seed_corpus(year, &module, &name, &source).await?;
+    }
+
+    tokio::fs::write(&manifest_path, manifest).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn test_add_fuzz_target() {
+        let manifest = add_fuzz_target(FUZZ_MANIFEST, "aoc2023_day02").unwrap().unwrap();
+        let manifest = add_fuzz_target(&manifest, "aoc2023_day01").unwrap().unwrap();
+        assert_eq!(add_fuzz_target(&manifest, "aoc2023_day02").unwrap(), None);
+        assert!(manifest.starts_with(FUZZ_MANIFEST));
+        assert!(manifest.ends_with(
+            "[[bin]]\nname = \"aoc2023_day01\"\npath = \"fuzz_targets/aoc2023_day01.rs\"\ntest = \
+             false\ndoc = false\nbench = false\n\n[[bin]]\nname = \"aoc2023_day02\"\npath = \
+             \"fuzz_targets/aoc2023_day02.rs\"\ntest = false\ndoc = false\nbench = false\n"
+        ));
+    }
+
+    #[test]
+    fn test_add_dependencies() {
+        let year = "[package]\nname = \"aoc2023\"\n\n[dependencies]\nanyhow = \"1.0.75\"\ncommon \
+                    = { path = \"../common\" }\nrustc-hash = \"1.1.0\"\n";
+        let manifest = add_dependencies(FUZZ_MANIFEST, year).unwrap().unwrap();
+        assert!(manifest.contains(
+            "[dependencies]\nanyhow = \"1.0.75\"\ncommon = { path = \"../crates/common\" \
+             }\nlibfuzzer-sys = \"0.4.7\"\nrustc-hash = \"1.1.0\"\n"
+        ));
+        assert_eq!(add_dependencies(&manifest, year).unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_features() {
+        let declared = "[features]\nembed-inputs = []\nembed-encrypted-inputs = []\n";
+        assert!(FUZZ_MANIFEST.contains(declared));
+        assert_eq!(add_features(FUZZ_MANIFEST).unwrap(), None);
+
+        let old = "[package]\nname = \"aoc-fuzz\"\n\n[dependencies]\nlibfuzzer-sys = \"0.4.7\"\n";
+        let manifest = add_features(old).unwrap().unwrap();
+        assert!(manifest.ends_with(declared));
+        assert_eq!(add_features(&manifest).unwrap(), None);
+    }
+
+    #[test]
+    fn test_fuzz_target() {
+        let features = crate_features("#![feature(const_for)]\nfn main() {}").unwrap();
+        let target =
+            fuzz_target(2023, &format_ident!("day01alt"), &format_ident!("Day01Alt"), &features);
+        assert!(syn::parse_file(&target).is_ok());
+        assert!(target.contains("#![feature(const_for)]\n"));
+        assert!(target
+            .contains("#[path = \"../../crates/aoc2023/src/day01alt/mod.rs\"]\nmod day01alt;"));
+        assert!(target.contains("let problem = day01alt::Day01Alt {};"));
+    }
+
+    #[test]
+    fn test_example_inputs() {
+        let file = syn::parse_quote! {
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn test_part1_example() {
+                    let input = "1abc2\npqr3stu8vwx";
+                    assert_eq!(Day01 {}.solve_part1_with("2x3x4"), Solution::U32(58));
+                }
+                #[test]
+                fn test_part2_example() {
+                    assert_eq!(Day01 {}.solve_part2_with("2x3x4"), Solution::U32(34));
+                }
+                #[test]
+                fn test_part1_real_input() {
+                    assert_eq!(Day01 {}.solve_part1_with("not an example"), Solution::U32(1));
+                }
+            }
+        };
+        assert_eq!(example_inputs(&file), ["1abc2\npqr3stu8vwx", "2x3x4"]);
+    }
+
+    #[test]
+    fn test_example_inputs_from_macro() {
+        let file = syn::parse_quote! {
+            const EXAMPLE: &str = "RL\n\nAAA = (BBB, CCC)";
+            const OTHER: &str = "not an example";
+            common::aoc_test! {
+                Day08 {};
+                part1: Solution::U16(1), examples: [
+                    EXAMPLE => Solution::U16(2),
+                    "LLR" => Solution::U16(6),
+                ];
+                part2: Solution::Todo, examples: [EXAMPLE => Solution::U64(6)];
+            }
+        };
+        assert_eq!(example_inputs(&file), ["RL\n\nAAA = (BBB, CCC)", "LLR"]);
+    }
+}
@@ -0,0 +1,34 @@
+//! Snapshot tests for generated code. Each snapshot is kept in
+//! `fixtures/golden`; after an intended change to the output, rerun the tests
+//! with `UPDATE_GOLDEN=1` to rewrite them and review the diff.
+
+use std::path::PathBuf;
+
+const UPDATE_VAR: &str = "UPDATE_GOLDEN";
+
+pub fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Can't read {:?}: {}", path, e))
+}
+
+/// Panics with a diff if `actual` doesn't match the snapshot called `name`.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/golden").join(name);
+    if std::env::var_os(UPDATE_VAR).is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("No snapshot at {:?}, run with {}=1 to create it", path, UPDATE_VAR)
+    });
+    if expected != actual {
+        panic!(
+            "Output differs from {:?}, run with {}=1 if that's intended:\n{}",
+            path,
+            UPDATE_VAR,
+            diffy::create_patch(&expected, actual)
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    path::PathBuf,
+    sync::Once,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use config_better::Config;
+use reqwest::{header::RETRY_AFTER, StatusCode};
+use tokio::sync::Mutex;
+use tracing::*;
+
+use crate::env::Environment;
+
+/// Keeps requests made by this run in order; the state file takes care of
+/// separate runs.
+static LAST_REQUEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Clone, Debug)]
+pub struct HttpConfig {
+    pub user_agent: String,
+    pub min_interval: Duration,
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after it.
+    pub base_backoff: Duration,
+    /// File holding when the last request was sent, in milliseconds since the
+    /// Unix epoch.
+    pub state_path: PathBuf,
+    /// Whether `AOC_CONTACT` was set. Only checked once a client is built, so
+    /// commands that never go online don't warn about it.
+    pub has_contact: bool,
+}
+
+impl HttpConfig {
+    pub fn from_env(env: &Environment) -> Self {
+        let contact = env.aoc_contact.clone().unwrap_or_else(|| "no contact given".to_owned());
+        Self {
+            user_agent: format!(
+                "{}/{} (github.com/kade-robertson/aoc by {})",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION"),
+                contact
+            ),
+            min_interval: Duration::from_millis(env.aoc_request_interval_ms),
+            max_retries: env.aoc_max_retries,
+            base_backoff: Duration::from_secs(1),
+            state_path: Config::new("aoc-update").cache.path.join("last_request"),
+            has_contact: env.aoc_contact.is_some(),
+        }
+    }
+}
+
+/// A client that identifies itself, spaces out its requests and retries
+/// transient failures.
+pub struct PoliteClient {
+    client: reqwest::Client,
+    config: HttpConfig,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// Only the delay-seconds form of `Retry-After` is understood, an HTTP date
+/// falls back to the usual backoff.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+impl PoliteClient {
+    pub fn new(builder: reqwest::ClientBuilder, config: HttpConfig) -> reqwest::Result<Self> {
+        // Several clients are built when fetching for several accounts.
+        static WARN_CONTACT: Once = Once::new();
+        if !config.has_contact {
+            WARN_CONTACT.call_once(|| {
+                warn!(
+                    "AOC_CONTACT is not set, please add an email or URL the site can reach you at"
+                )
+            });
+        }
+        let client = builder.user_agent(config.user_agent.clone()).build()?;
+        Ok(Self { client, config })
+    }
+
+    /// Sends a GET request, retrying rate limited requests, server errors and
+    /// failed connections. Once out of retries the last response or error is
+    /// returned as is.
+    pub async fn get(&self, url: &str) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            self.wait_turn().await;
+            let result = self.client.get(url).send().await;
+            let requested_delay = match &result {
+                Ok(response)
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS
+                        || response.status().is_server_error() =>
+                {
+                    retry_after(response)
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => None,
+                _ => return result,
+            };
+            if attempt >= self.config.max_retries {
+                return result;
+            }
+
+            let delay = requested_delay.unwrap_or(self.config.base_backoff * 2u32.pow(attempt));
+            match &result {
+                Ok(response) => {
+                    warn!("{} returned {}, retrying in {:?}", url, response.status(), delay)
+                }
+                Err(e) => warn!("Request to {} failed ({}), retrying in {:?}", url, e, delay),
+            }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Waits until the minimum interval has passed since the last request, then
+    /// records this one.
+    async fn wait_turn(&self) {
+        let _guard = LAST_REQUEST_LOCK.lock().await;
+        let last = tokio::fs::read_to_string(&self.config.state_path)
+            .await
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or_default();
+        let next = last.saturating_add(self.config.min_interval.as_millis() as u64);
+        if let Some(wait) = next.checked_sub(now_millis()).filter(|&w| w > 0) {
+            debug!("Waiting {}ms before the next request", wait);
+            tokio::time::sleep(Duration::from_millis(wait)).await;
+        }
+
+        // Losing track of the last request only makes the next run less polite,
+        // so this isn't worth failing over.
+        if let Some(parent) = self.config.state_path.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
+        let _ = tokio::fs::write(&self.config.state_path, now_millis().to_string()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    use super::*;
+
+    /// Serves one canned response per connection, returning the requests it
+    /// received.
+    async fn mock_server(responses: Vec<&'static str>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/2023/day/1/input", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                requests.push(String::from_utf8(request).unwrap().to_lowercase());
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn test_config(name: &str, min_interval: Duration) -> HttpConfig {
+        HttpConfig {
+            user_agent: "aoc-update-test (test@example.com)".to_owned(),
+            min_interval,
+            max_retries: 2,
+            base_backoff: Duration::from_millis(10),
+            state_path: std::env::temp_dir().join(format!(
+                "aoc-update-{}-{}",
+                name,
+                std::process::id()
+            )),
+            has_contact: true,
+        }
+    }
+
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+    const SERVER_ERROR: &str =
+        "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    #[tokio::test]
+    async fn test_honours_retry_after() {
+        let (url, server) = mock_server(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: \
+             close\r\n\r\n",
+            OK,
+        ])
+        .await;
+        let config = test_config("retry-after", Duration::ZERO);
+        let client = PoliteClient::new(reqwest::ClientBuilder::new(), config.clone()).unwrap();
+
+        let start = Instant::now();
+        let response = client.get(&url).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(response.text().await.unwrap(), "ok");
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].contains("user-agent: aoc-update-test (test@example.com)"));
+        let _ = std::fs::remove_file(config.state_path);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, server) = mock_server(vec![SERVER_ERROR; 3]).await;
+        let config = test_config("max-retries", Duration::ZERO);
+        let client = PoliteClient::new(reqwest::ClientBuilder::new(), config.clone()).unwrap();
+
+        let response = client.get(&url).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(server.await.unwrap().len(), 3);
+        let _ = std::fs::remove_file(config.state_path);
+    }
+
+    #[tokio::test]
+    async fn test_min_interval_persists() {
+        let (url, server) = mock_server(vec![OK, OK]).await;
+        let config = test_config("interval", Duration::from_millis(300));
+
+        // Separate clients stand in for separate runs, sharing only the state
+        // file.
+        let start = Instant::now();
+        for _ in 0..2 {
+            let client = PoliteClient::new(reqwest::ClientBuilder::new(), config.clone()).unwrap();
+            client.get(&url).await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert!(std::fs::read_to_string(&config.state_path).unwrap().parse::<u64>().is_ok());
+
+        server.await.unwrap();
+        let _ = std::fs::remove_file(config.state_path);
+    }
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use common::{
+    crypt::InputKey,
+    input::{encrypted_input_path, input_path},
+    input_sets::{account_dir, answers_path, input_sets, ACCOUNTS_DIR},
+};
+use tracing::*;
+
+use crate::{
+    accounts::{write_private, Account},
+    get_answers, get_input,
+    http::HttpConfig,
+};
+
+pub const INPUTS_DIR: &str = "inputs";
+
+/// Stores a freshly fetched input in `inputs_dir`, encrypted with the local
+/// input key.
+pub async fn write_input(inputs_dir: &Path, year: u32, day: u8, input: &str) -> anyhow::Result<()> {
+    let key = InputKey::load()?;
+    let day_name = format!("day{:02}", day);
+    let encrypted_path = encrypted_input_path(inputs_dir, year as u16, &day_name);
+    if let Some(parent) = encrypted_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&encrypted_path, key.encrypt(input)).await?;
+
+    // A stale plaintext copy would otherwise take priority when loading.
+    let plain_path = input_path(inputs_dir, year as u16, &day_name);
+    if tokio::fs::try_exists(&plain_path).await? {
+        tokio::fs::remove_file(&plain_path).await?;
+    }
+
+    info!("Wrote encrypted input to {:?}", encrypted_path);
+    Ok(())
+}
+
+/// Stores the answers the site accepted for this input, if there are any.
+pub async fn write_answers(
+    inputs_dir: &Path,
+    year: u32,
+    day: u8,
+    answers: &[Option<String>; 2],
+) -> anyhow::Result<()> {
+    if answers.iter().all(Option::is_none) {
+        return Ok(());
+    }
+    let path = answers_path(inputs_dir, year as u16, &format!("day{:02}", day));
+    let [part1, part2] = answers.clone().map(Option::unwrap_or_default);
+    tokio::fs::write(&path, format!("{}\n{}\n", part1, part2)).await?;
+
+    info!("Wrote accepted answers to {:?}", path);
+    Ok(())
+}
+
+/// Fetches the input and any accepted answers for every account.
+pub async fn fetch_all(
+    accounts: &[Account],
+    config: &HttpConfig,
+    year: u32,
+    day: u8,
+) -> anyhow::Result<()> {
+    for account in accounts {
+        info!(account = account.name, "Fetching input");
+        let client = account.client(config)?;
+        let dir = account_dir(Path::new(INPUTS_DIR), &account.name);
+        let input = get_input(&client, year, day).await.map_err(|e| account.expired_or(e))?;
+        write_input(&dir, year, day, &input).await?;
+        let answers = get_answers(&client, year, day).await.map_err(|e| account.expired_or(e))?;
+        write_answers(&dir, year, day, &answers).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn gen_key() -> anyhow::Result<()> {
+    let path = InputKey::default_path();
+    if tokio::fs::try_exists(&path).await? {
+        anyhow::bail!("An input key already exists at {:?}, refusing to replace it", path);
+    }
+    write_private(&path, &InputKey::generate().to_hex()).await?;
+
+    info!(
+        "Wrote a new input key to {:?}. Share it with teammates privately, never commit it",
+        path
+    );
+    Ok(())
+}
+
+/// Every input file with `extension` in one input set's directory, for every
+/// year or just `year`.
+async fn input_files(
+    dir: &Path,
+    extension: &str,
+    year: Option<u32>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut years = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = years.next_entry().await? {
+        if !entry.metadata().await?.is_dir() || entry.file_name() == ACCOUNTS_DIR {
+            continue;
+        }
+        if year.is_some_and(|year| entry.file_name() != year.to_string().as_str()) {
+            continue;
+        }
+        let mut days = tokio::fs::read_dir(entry.path()).await?;
+        while let Some(day) = days.next_entry().await? {
+            let path = day.path();
+            if path.extension().is_some_and(|e| e == extension) {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+async fn encrypt_dir(inputs_dir: &Path, key: &InputKey) -> anyhow::Result<()> {
+    for set in input_sets(inputs_dir) {
+        for path in input_files(&set.dir, "txt", None).await? {
+            let input = tokio::fs::read_to_string(&path).await?;
+            tokio::fs::write(path.with_extension("enc"), key.encrypt(&input)).await?;
+            tokio::fs::remove_file(&path).await?;
+            info!("Encrypted {:?}", path);
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts every plaintext input under `inputs/`, including each account's,
+/// and removes the originals.
+pub async fn encrypt_inputs() -> anyhow::Result<()> {
+    encrypt_dir(Path::new(INPUTS_DIR), &InputKey::load()?).await
+}
+
+async fn decrypt_dir(inputs_dir: &Path, key: &InputKey, year: Option<u32>) -> anyhow::Result<()> {
+    for set in input_sets(inputs_dir) {
+        for path in input_files(&set.dir, "enc", year).await? {
+            let input = key.decrypt(&tokio::fs::read(&path).await?).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not decrypt {:?}, it was encrypted with a different key",
+                    path
+                )
+            })?;
+            tokio::fs::write(path.with_extension("txt"), input).await?;
+            info!("Decrypted {:?}", path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes a plaintext copy of every encrypted input under `inputs/`, or only
+/// those for `year`, keeping the encrypted files. Embedding plaintext inputs
+/// with the `embed-inputs` feature needs these, since only the encrypted
+/// inputs are committed.
+pub async fn decrypt_inputs(year: Option<u32>) -> anyhow::Result<()> {
+    decrypt_dir(Path::new(INPUTS_DIR), &InputKey::load()?, year).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_encrypt_and_decrypt_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc-update-encrypt-{}", std::process::id()));
+        let account = account_dir(&dir, "alt");
+        for inputs in [&dir, &account] {
+            std::fs::create_dir_all(inputs.join("2023")).unwrap();
+            std::fs::write(input_path(inputs, 2023, "day01"), "1abc2\n").unwrap();
+        }
+
+        let key = InputKey::generate();
+        encrypt_dir(&dir, &key).await.unwrap();
+
+        for inputs in [&dir, &account] {
+            assert!(!input_path(inputs, 2023, "day01").exists());
+            let encrypted = std::fs::read(encrypted_input_path(inputs, 2023, "day01")).unwrap();
+            assert_eq!(key.decrypt(&encrypted).as_deref(), Some("1abc2\n"));
+        }
+
+        decrypt_dir(&dir, &key, Some(2022)).await.unwrap();
+        assert!(!input_path(&dir, 2023, "day01").exists());
+        decrypt_dir(&dir, &key, Some(2023)).await.unwrap();
+        for inputs in [&dir, &account] {
+            let input = std::fs::read_to_string(input_path(inputs, 2023, "day01")).unwrap();
+            assert_eq!(input, "1abc2\n");
+            assert!(encrypted_input_path(inputs, 2023, "day01").exists());
+        }
+        assert!(decrypt_dir(&dir, &InputKey::generate(), None).await.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use common::progress::days_in_event;
+use config_better::Config;
+use serde::Deserialize;
+use time::{format_description::FormatItem, macros::format_description, OffsetDateTime};
+use tracing::*;
+
+use crate::{
+    accounts::{fetch_page, load_account, load_accounts, Account},
+    http::HttpConfig,
+};
+
+/// The site asks that private leaderboards are requested at most once every 15
+/// minutes.
+const CACHE_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+const TIMESTAMP_FORMAT: &[FormatItem] =
+    format_description!("[month]-[day] [hour]:[minute]:[second]");
+
+#[derive(Deserialize, Debug)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for members who haven't set a public name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// Keyed by day, then part.
+    pub completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star_time(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level.get(&day)?.get(&part).map(|s| s.get_star_ts)
+    }
+
+    fn day_marker(&self, day: u8) -> char {
+        match (self.star_time(day, 1), self.star_time(day, 2)) {
+            (Some(_), Some(_)) => '*',
+            (Some(_), None) => '+',
+            _ => '.',
+        }
+    }
+}
+
+/// Formats a star timestamp in the puzzles' own US Eastern time.
+fn format_timestamp(ts: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(ts)
+        .ok()
+        .filter(|_| ts > 0)
+        .and_then(|t| t.to_offset(crate::wait::UNLOCK_OFFSET).format(TIMESTAMP_FORMAT).ok())
+        .unwrap_or_else(|| "-".to_owned())
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Members ordered the way the site shows them: by local score, then by
+    /// who got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    /// Days in the leaderboard's event, 25 if the event isn't a year.
+    fn days(&self) -> u8 {
+        self.event.parse().map_or(25, days_in_event)
+    }
+
+    fn owner_name(&self) -> String {
+        self.members
+            .values()
+            .find(|m| m.id == self.owner_id)
+            .map_or_else(|| format!("user #{}", self.owner_id), Member::display_name)
+    }
+
+    /// One row per member, with `*` for both stars on a day and `+` for only
+    /// the first.
+    pub fn render(&self) -> String {
+        let members = self.ranked();
+        let name_width = members.iter().map(|m| m.display_name().len()).max().unwrap_or(0).max(4);
+        let days = self.days();
+
+        let mut out = format!("{} private leaderboard of {}\n\n", self.event, self.owner_name());
+        out.push_str(&format!(
+            "{:>3}  {:<name_width$}  {:>5}  {:>5}  ",
+            "#", "Name", "Score", "Stars"
+        ));
+        for day in 1..=days {
+            out.push(if day % 5 == 0 { char::from(b'0' + day / 10) } else { ' ' });
+        }
+        out.push_str("  Last star\n");
+        out.push_str(&format!("{:>3}  {:<name_width$}  {:>5}  {:>5}  ", "", "", "", ""));
+        for day in 1..=days {
+            out.push(char::from(b'0' + day % 10));
+        }
+        out.push('\n');
+
+        for (rank, member) in members.iter().enumerate() {
+            out.push_str(&format!(
+                "{:>3}  {:<name_width$}  {:>5}  {:>5}  ",
+                rank + 1,
+                member.display_name(),
+                member.local_score,
+                member.stars
+            ));
+            out.extend((1..=days).map(|day| member.day_marker(day)));
+            out.push_str(&format!("  {}\n", format_timestamp(member.last_star_ts)));
+        }
+        out
+    }
+
+    /// Completion times for both parts of one day, fastest first.
+    pub fn render_day(&self, day: u8) -> String {
+        let mut members = self
+            .members
+            .values()
+            .filter_map(|m| Some((m.star_time(day, 1)?, m.star_time(day, 2), m)))
+            .collect::<Vec<_>>();
+        members.sort_by_key(|&(part1, part2, m)| (part2.unwrap_or(i64::MAX), part1, m.id));
+        let name_width =
+            members.iter().map(|(.., m)| m.display_name().len()).max().unwrap_or(0).max(4);
+
+        let mut out = format!("{:<name_width$}  {:<14}  {}\n", "Name", "Part 1", "Part 2");
+        for (part1, part2, member) in members {
+            out.push_str(&format!(
+                "{:<name_width$}  {:<14}  {}\n",
+                member.display_name(),
+                format_timestamp(part1),
+                part2.map_or_else(|| "-".to_owned(), format_timestamp)
+            ));
+        }
+        out
+    }
+}
+
+/// Each account can see different leaderboards, so caches aren't shared.
+fn cache_path(account: &str, year: u32, id: &str) -> PathBuf {
+    Config::new("aoc-update")
+        .cache
+        .path
+        .join(format!("leaderboard-{}-{}-{}.json", account, year, id))
+}
+
+async fn read_cache(path: &PathBuf) -> Option<String> {
+    let age = tokio::fs::metadata(path).await.ok()?.modified().ok()?.elapsed().ok()?;
+    if age > CACHE_LIFETIME {
+        return None;
+    }
+    info!("Using leaderboard cached {}s ago", age.as_secs());
+    tokio::fs::read_to_string(path).await.ok()
+}
+
+/// Fetches a private leaderboard, reusing a cached copy if it is recent enough.
+pub async fn fetch(
+    account: &Account,
+    http_config: &HttpConfig,
+    year: u32,
+    id: &str,
+) -> anyhow::Result<Leaderboard> {
+    let path = cache_path(&account.name, year, id);
+    if let Some(json) = read_cache(&path).await {
+        return Ok(Leaderboard::parse(&json)?);
+    }
+
+    let url = format!("https://adventofcode.com/{}/leaderboard/private/view/{}.json", year, id);
+    let json =
+        fetch_page(&account.client(http_config)?, &url).await.map_err(|e| account.expired_or(e))?;
+    let leaderboard = Leaderboard::parse(&json)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&path, json).await?;
+
+    Ok(leaderboard)
+}
+
+/// Splits `--account <name>` out of the arguments, wherever it is.
+fn take_account(args: &[String]) -> anyhow::Result<(Option<&str>, Vec<&String>)> {
+    let mut account = None;
+    let mut rest = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--account" {
+            let name = args.next().ok_or_else(|| anyhow::anyhow!("--account needs a name"))?;
+            account = Some(name.as_str());
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((account, rest))
+}
+
+/// Handles `aoc-update leaderboard <year> <id> [day] [--account <name>]`. The
+/// first account is used unless another is named.
+pub async fn run(http_config: &HttpConfig, args: &[String]) -> anyhow::Result<()> {
+    let (account, args) = take_account(args)?;
+    let (year, id, day) = match args[..] {
+        [year, id] => (year.parse()?, id, None),
+        [year, id, day] => (year.parse()?, id, Some(day.parse()?)),
+        _ => {
+            error!("Usage: aoc-update leaderboard <year> <id> [day] [--account <name>]");
+            return Ok(());
+        }
+    };
+
+    let account = match account {
+        Some(name) => load_account(name).await?,
+        None => load_accounts().await?.remove(0),
+    };
+    let leaderboard = fetch(&account, http_config, year, id).await?;
+    match day {
+        Some(day) => print!("{}", leaderboard.render_day(day)),
+        None => print!("{}", leaderboard.render()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn test_take_account() {
+        let args = ["2023", "--account", "alt", "1234"].map(String::from);
+        let (account, rest) = take_account(&args).unwrap();
+        assert_eq!(account, Some("alt"));
+        assert_eq!(rest, ["2023", "1234"]);
+        assert_eq!(take_account(&args[..2]).unwrap_err().to_string(), "--account needs a name");
+        assert_ne!(cache_path("alt", 2023, "1234"), cache_path("default", 2023, "1234"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, "2023");
+        assert_eq!(leaderboard.owner_id, 1001);
+
+        let ranked = leaderboard.ranked();
+        assert_eq!(ranked.iter().map(|m| m.id).collect::<Vec<_>>(), [1001, 1002, 1003]);
+        assert_eq!(ranked[0].star_time(3, 1), Some(1701584400));
+        assert_eq!(ranked[0].star_time(3, 2), None);
+        assert_eq!(ranked[1].display_name(), "(anonymous user #1002)");
+        assert!(ranked[2].completion_day_level.is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = Leaderboard::parse(FIXTURE).unwrap().render();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "2023 private leaderboard of Ada Lovelace");
+        assert_eq!(
+            lines[4],
+            "  1  Ada Lovelace               26      5  **+......................  12-03 01:20:00"
+        );
+        assert!(lines[5].contains("(anonymous user #1002)"));
+        assert!(lines[6].ends_with(".........................  -"));
+    }
+
+    #[test]
+    fn test_render_short_event() {
+        let mut leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        leaderboard.event = "2025".to_owned();
+        let rendered = leaderboard.render();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[3].ends_with("  123456789012"));
+        assert!(lines[4].ends_with("  **+.........  12-03 01:20:00"));
+    }
+
+    #[test]
+    fn test_render_day() {
+        let rendered = Leaderboard::parse(FIXTURE).unwrap().render_day(1);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("Ada Lovelace"));
+        assert!(lines[1].ends_with("12-01 00:10:00  12-01 00:20:00"));
+        assert_eq!(Leaderboard::parse(FIXTURE).unwrap().render_day(3).lines().count(), 2);
+    }
+}
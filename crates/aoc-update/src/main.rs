@@ -1,23 +1,46 @@
-use std::{path::Path, sync::Arc};
+use std::path::{Path, PathBuf};
 
-use config_better::Config;
-use proc_macro2::{Ident, TokenStream};
+use common::{examples::EXAMPLES_DIR, input_sets::DEFAULT_ACCOUNT};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use syn::parse::Parser;
 use tracing::*;
 
-use crate::{env::Environment, output::question_comment};
+use crate::{
+    env::Environment,
+    http::{HttpConfig, PoliteClient},
+    output::question_comment,
+    templates::Templates,
+};
 
+mod accounts;
+mod description;
 mod env;
 mod env_logger;
+mod examples;
+mod fuzz;
+#[cfg(test)]
+mod golden;
+mod http;
+mod inputs;
+mod leaderboard;
+mod new_year;
 mod output;
-
+mod pretty;
+mod templates;
+mod variant;
+mod verify;
+mod wait;
+
+/// Fetches a day's title, its description as doc comments and the code blocks
+/// in each part's description, which are usually the examples.
 async fn get_description(
-    client: &reqwest::Client,
+    client: &PoliteClient,
     year: u32,
     day: u8,
-) -> anyhow::Result<(String, TokenStream)> {
+) -> anyhow::Result<(String, TokenStream, Vec<Vec<String>>)> {
     let question_url = format!("https://adventofcode.com/{}/day/{}", year, day);
-    let question_page = client.get(&question_url).send().await?.text().await?;
+    let question_page = accounts::fetch_page(client, &question_url).await?;
     let fragment = scraper::Html::parse_fragment(&question_page);
     let title_selector =
         scraper::Selector::parse("article.day-desc:nth-child(1) > h2:nth-child(1)")
@@ -30,27 +53,50 @@ async fn get_description(
 
     let selector = scraper::Selector::parse("article.day-desc")
         .map_err(|_| anyhow::anyhow!("Bad selector"))?;
-    let mut doc_select = fragment.select(&selector);
-    let question_md = doc_select
-        .next()
-        .map(|q| html2md::parse_html(&q.inner_html()))
+    let articles = fragment.select(&selector).collect::<Vec<_>>();
+    let question_md = articles
+        .first()
+        .map(|q| description::to_markdown(*q))
         .ok_or(anyhow::anyhow!("No article found"))?;
-    let maybe_second_part = doc_select
-        .next()
-        .map(|q| html2md::parse_html(&q.inner_html()))
-        .unwrap_or_else(|| "".to_owned());
+    let maybe_second_part =
+        articles.get(1).map(|q| description::to_markdown(*q)).unwrap_or_else(|| "".to_owned());
     let combined = question_md + "\n\n" + &maybe_second_part;
+    let examples =
+        articles.into_iter().take(2).map(examples::code_blocks).collect::<anyhow::Result<_>>()?;
 
-    Ok((title, question_comment(&combined)))
+    Ok((title, question_comment(&combined), examples))
 }
 
-async fn get_input(client: &reqwest::Client, year: u32, day: u8) -> anyhow::Result<String> {
+async fn get_input(client: &PoliteClient, year: u32, day: u8) -> anyhow::Result<String> {
     let input_url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-    let input = client.get(&input_url).send().await?.text().await?;
+    let input = accounts::fetch_page(client, &input_url).await?;
 
     Ok(input)
 }
 
+/// Scrapes the answers the site has accepted for this account, which are shown
+/// on the puzzle page once a part is solved.
+async fn get_answers(
+    client: &PoliteClient,
+    year: u32,
+    day: u8,
+) -> anyhow::Result<[Option<String>; 2]> {
+    let question_url = format!("https://adventofcode.com/{}/day/{}", year, day);
+    let question_page = accounts::fetch_page(client, &question_url).await?;
+    let fragment = scraper::Html::parse_fragment(&question_page);
+    let selector =
+        scraper::Selector::parse("main > p").map_err(|_| anyhow::anyhow!("Bad selector"))?;
+    let code_selector =
+        scraper::Selector::parse("code").map_err(|_| anyhow::anyhow!("Bad selector"))?;
+
+    let mut answers = fragment
+        .select(&selector)
+        .filter(|p| p.text().any(|t| t.contains("Your puzzle answer was")))
+        .filter_map(|p| p.select(&code_selector).next())
+        .map(|code| code.text().collect::<String>());
+    Ok([answers.next(), answers.next()])
+}
+
 // For any continous string of letters, make the first letter uppercase.
 fn title_case(s: &str) -> String {
     let mut result = String::new();
@@ -71,79 +117,125 @@ fn title_case(s: &str) -> String {
     result
 }
 
-async fn update_main(year: u32) -> anyhow::Result<TokenStream> {
-    let base_path = format!("crates/aoc{}/src", year);
-    let output_path = Path::new(&base_path);
+fn src_dir(year: u32) -> PathBuf {
+    PathBuf::from(format!("crates/aoc{}/src", year))
+}
 
+/// Every `day*` module in a year's `src`, with the type it implements
+/// `Problem` for, sorted by module name.
+async fn day_modules(src: &Path) -> anyhow::Result<Vec<(Ident, Ident)>> {
     // Get the list of directories in src that start with day
-    let mut problems = tokio::fs::read_dir(output_path).await?;
-    let mut considered: Vec<Ident> = vec![];
-    let mut considered_upper: Vec<Ident> = vec![];
+    let mut problems = tokio::fs::read_dir(src).await?;
+    let mut considered: Vec<(Ident, Ident)> = vec![];
     while let Some(path) = problems.next_entry().await? {
         let metadata = path.metadata().await?;
         let filename = path.file_name().to_string_lossy().to_string();
         if metadata.is_dir() && filename.starts_with("day") {
-            considered.push(format_ident!("{}", filename));
-            considered_upper.push(format_ident!("{}", title_case(&filename)));
+            // Variants can't be named reliably from the directory, so prefer
+            // whatever the module actually implements `Problem` for.
+            let problem = tokio::fs::read_to_string(path.path().join("mod.rs"))
+                .await
+                .ok()
+                .and_then(|file| syn::parse_file(&file).ok())
+                .and_then(|file| variant::problem_struct(&file))
+                .unwrap_or_else(|| format_ident!("{}", title_case(&filename)));
+            considered.push((format_ident!("{}", filename), problem));
         }
     }
 
     considered.sort();
-    considered_upper.sort();
-
-    let main_content = quote! {
-        use mimalloc::MiMalloc;
-
-        #[global_allocator]
-        static GLOBAL: MiMalloc = MiMalloc;
-
-        use common::{Problem, BenchmarkCollection};
-
-        #( mod #considered; )*
+    Ok(considered)
+}
 
-        #( use #considered::*; )*
+/// The `#![feature(..)]` attributes at the top of a year's `main.rs`.
+fn crate_features(main: &str) -> anyhow::Result<String> {
+    let file = syn::parse_file(main)?;
+    let mut features = String::new();
+    for attr in file.attrs.iter().filter(|attr| attr.path().is_ident("feature")) {
+        let list = attr.meta.require_list()?.tokens.to_string().replace(" ,", ",");
+        features.push_str(&format!("#![feature({})]\n", list));
+    }
+    Ok(features)
+}
 
-        fn main() {
-            let args = std::env::args().collect::<Vec<_>>();
-            let problems: Vec<Box<dyn Problem>> = vec![
-                #( Box::new(#considered_upper) ),*
-            ];
+/// Renders `main.rs` for a year from its `(module, problem)` pairs, with
+/// `features` put before everything else.
+fn render_main(
+    year: u32,
+    modules: Vec<(Ident, Ident)>,
+    features: &str,
+    templates: &Templates,
+) -> anyhow::Result<String> {
+    let (considered, considered_upper): (Vec<Ident>, Vec<Ident>) = modules.into_iter().unzip();
+
+    templates::render(
+        &format!("{}{}", features, templates.main),
+        &[
+            ("year", year.to_string()),
+            ("modules", quote! { #( mod #considered; )* }.to_string()),
+            ("uses", quote! { #( use #considered::*; )* }.to_string()),
+            ("problems", quote! { #( Box::new(#considered_upper) ),* }.to_string()),
+        ],
+    )
+}
 
-            if args.contains(&"bench".to_string()) {
-                for problem in problems {
-                    let bench = problem.bench_part1();
-                    println!("{} - Part 1: {:?} ({} runs)", problem.name(), bench.average(), bench.results.len());
+/// Renders `main.rs` for a year, registering every `day*` module in its `src`.
+/// Any `#![feature]`s the current `main.rs` enables are kept, since days may
+/// rely on them.
+async fn update_main(year: u32, templates: &Templates) -> anyhow::Result<String> {
+    let src = src_dir(year);
+    let features = match tokio::fs::read_to_string(src.join("main.rs")).await {
+        Ok(main) => crate_features(&main)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    render_main(year, day_modules(&src).await?, &features, templates)
+}
 
-                    let bench = problem.bench_part2();
-                    println!("{} - Part 2: {:?} ({} runs)", problem.name(), bench.average(), bench.results.len());
-                }
-            } else if args.contains(&"bench-md".to_string()) {
-                let mut collection = BenchmarkCollection::new(format!("Advent of Code {}", #year));
-                for problem in problems {
-                    let bench = problem.bench_part1();
-                    collection.add(bench);
+/// Renders a new day from the day template.
+fn render_day(
+    templates: &Templates,
+    year: u32,
+    day: u8,
+    title: &str,
+    description: &TokenStream,
+) -> anyhow::Result<String> {
+    templates::render(
+        &templates.day,
+        &[
+            ("year", year.to_string()),
+            ("day", day.to_string()),
+            ("module", format!("day{:02}", day)),
+            ("struct", format!("Day{:02}", day)),
+            ("title", Literal::string(title).to_string()),
+            ("description", description.to_string()),
+        ],
+    )
+}
 
-                    let bench = problem.bench_part2();
-                    collection.add(bench);
-                }
-                println!("{}", collection.to_markdown());
-            } else {
-                for problem in problems {
-                    println!("{} - Part 1: {}", problem.name(), problem.solve_part1());
-                    println!("{} - Part 2: {}", problem.name(), problem.solve_part2());
-                }
+/// Replaces the doc comment on a day's `Problem` struct with a fresh
+/// description, keeping everything else, helper structs included.
+fn replace_description(source: &str, description: &TokenStream) -> anyhow::Result<String> {
+    let mut file = syn::parse_file(source)?;
+    let problem =
+        variant::problem_struct(&file).ok_or_else(|| anyhow::anyhow!("No `impl Problem` found"))?;
+
+    for item in file.items.iter_mut() {
+        if let syn::Item::Struct(s) = item {
+            if s.ident == problem {
+                s.attrs.retain(|attr| !attr.path().is_ident("doc"));
+                let attrs = syn::Attribute::parse_outer.parse2(description.clone())?;
+                s.attrs.splice(0..0, attrs);
             }
         }
-    };
+    }
 
-    Ok(main_content)
+    Ok(pretty::unparse(&file))
 }
 
-async fn write_main(year: u32) -> anyhow::Result<()> {
-    let main_content = update_main(year).await?;
-    let main_file = prettyplease::unparse(&syn::parse_file(&main_content.to_string())?);
-
-    tokio::fs::write(Path::new(&format!("crates/aoc{}/src/main.rs", year)), main_file).await?;
+async fn write_main(year: u32, templates: &Templates) -> anyhow::Result<()> {
+    let main_file = update_main(year, templates).await?;
+    tokio::fs::write(src_dir(year).join("main.rs"), main_file).await?;
 
     Ok(())
 }
@@ -153,155 +245,149 @@ async fn main() -> anyhow::Result<()> {
     let _ = dotenvy::dotenv();
     let env = envy::from_env::<Environment>()?;
     env.init_logger();
+    let http_config = HttpConfig::from_env(&env);
+    let templates = Templates::load(Path::new(&env.aoc_templates_dir)).await?;
 
     let args = std::env::args().collect::<Vec<_>>();
+    match args.get(1).map(|a| a.as_str()) {
+        Some("gen-key") => return inputs::gen_key().await,
+        Some("encrypt-inputs") => return inputs::encrypt_inputs().await,
+        Some("decrypt-inputs") => {
+            return inputs::decrypt_inputs(args.get(2).map(|y| y.parse()).transpose()?).await
+        }
+        Some("account") => return accounts::run(&args[2..], &http_config).await,
+        Some("login") => {
+            return accounts::login(
+                args.get(2).map_or(DEFAULT_ACCOUNT, |a| a.as_str()),
+                &http_config,
+            )
+            .await
+        }
+        Some("whoami") => {
+            return accounts::whoami(args.get(2).map(|a| a.as_str()), &http_config).await
+        }
+        Some("new-year") if args.len() >= 3 => {
+            return new_year::new_year(args[2].parse()?, &templates).await
+        }
+        Some("variant") if args.len() >= 5 => {
+            return variant::new_variant(&templates, args[2].parse()?, args[3].parse()?, &args[4])
+                .await
+        }
+        Some("fuzz") if args.len() >= 3 => return fuzz::write_targets(args[2].parse()?).await,
+        Some("verify") if args.len() >= 3 => return verify::run(&templates, &args[2]).await,
+        Some("leaderboard") => return leaderboard::run(&http_config, &args[2..]).await,
+        Some("today") => return wait::today(&http_config, &templates).await,
+        Some("wait") if args.len() >= 4 => {
+            return wait::wait(&http_config, &templates, args[2].parse()?, args[3].parse()?).await
+        }
+        _ => {}
+    }
     if args.len() < 3 {
-        error!("Usage: aoc-update <year> <day>");
+        error!(
+            "Usage: aoc-update <year> <day> | new-year <year> | variant <year> <day> <name> | wait <year> <day> | today | leaderboard | login | whoami | \
+             account | fuzz <year> | verify <year|all> | gen-key | encrypt-inputs | \
+             decrypt-inputs [year]"
+        );
         return Ok(());
     }
 
     let year = args[1].parse::<u32>()?;
     if args[2] == "only-main" {
-        write_main(year).await?;
+        write_main(year, &templates).await?;
         return Ok(());
     }
     let day = args[2].parse::<u8>()?;
 
-    info!(year, day, "Fetching question");
-
-    let config_dirs = Config::new("aoc-update");
-    config_dirs.config.create_async().await?;
-    let token_path = config_dirs.config.path.join("token.txt");
-
-    info!("Reading auth token from {:?}", token_path);
-
-    let token = tokio::fs::read_to_string(&token_path).await.inspect_err(|e| {
-        error!("Failed to read token: {}", e);
-    })?;
+    update_day(&http_config, &templates, year, day, args.get(3).map(|a| a.as_str())).await
+}
 
-    info!("Read token!");
+/// Scaffolds a day and fetches its inputs and examples. `mode` limits this to
+/// just the input (`only-input`) or just the description and examples
+/// (`only-desc`).
+async fn update_day(
+    http_config: &HttpConfig,
+    templates: &Templates,
+    year: u32,
+    day: u8,
+    mode: Option<&str>,
+) -> anyhow::Result<()> {
+    info!(year, day, "Fetching question");
 
-    let token_cookie = format!("session={}; Domain=adventofcode.com", token);
-    let cookie_jar = Arc::new(reqwest::cookie::Jar::default());
-    cookie_jar.add_cookie_str(&token_cookie, &"https://adventofcode.com".parse::<reqwest::Url>()?);
-    let client = reqwest::ClientBuilder::new().cookie_provider(cookie_jar).build()?;
+    let accounts = accounts::load_accounts().await?;
+    let client = accounts[0].client(http_config)?;
 
-    let base_path = format!("crates/aoc{}/src/day{:02}", year, day);
-    let output_path = Path::new(&base_path);
-    if args.len() > 3 && args[3] == "only-input" {
-        tokio::fs::create_dir_all(output_path).await?;
-        tokio::fs::write(
-            output_path.join("input.txt"),
-            get_input(&client, year, day).await.unwrap_or_default(),
-        )
-        .await?;
+    let output_path = &src_dir(year).join(format!("day{:02}", day));
+    if mode == Some("only-input") {
+        inputs::fetch_all(&accounts, http_config, year, day).await?;
         return Ok(());
     }
 
-    let (title, comment_preamble) = get_description(&client, year, day).await?;
-    if args.len() > 3 && args[3] == "only-desc" {
-        // Read the existing file, and replace the comment block at the top with the
-        // fresh description.
+    let (title, comment_preamble, example_blocks) =
+        get_description(&client, year, day).await.map_err(|e| accounts[0].expired_or(e))?;
+    examples::write_examples(&output_path.join(EXAMPLES_DIR), &example_blocks).await?;
+    if mode == Some("only-desc") {
         let file = tokio::fs::read_to_string(output_path.join("mod.rs")).await?;
-        let tokens = syn::parse_file(&file)?;
-
-        let mut new_stream = TokenStream::new();
-
-        for t in tokens.items {
-            match t {
-                syn::Item::Struct(mut s) => {
-                    if !s.ident.to_string().starts_with("Day") {
-                        new_stream.extend(quote! { #s });
-                    } else {
-                        s.attrs.clear();
-                        new_stream.extend(quote! {
-                            #comment_preamble
-                            #s
-                        });
-                    }
-                }
-                _ => {
-                    new_stream.extend(quote! { #t });
-                }
-            }
-        }
-        tokio::fs::write(
-            output_path.join("mod.rs"),
-            prettyplease::unparse(&syn::parse_file(&new_stream.to_string())?),
-        )
-        .await?;
+        let updated = replace_description(&file, &comment_preamble)?;
+        tokio::fs::write(output_path.join("mod.rs"), updated).await?;
         return Ok(());
     }
 
-    let problem_ident = format_ident!("Day{:02}", day);
-
-    let final_doc = quote! {
-        use common::{Problem, Solution};
-
-        #comment_preamble
-        pub struct #problem_ident;
-
-        impl Problem for #problem_ident {
-            fn problem_input(&self) -> &'static str {
-                include_str!("input.txt")
-            }
-
-            fn day(&self) -> u8 {
-               #day
-            }
-
-            fn name(&self) -> &str {
-                #title
-            }
-
-            fn solve_part1_with(&self, input: &str) -> Solution {
-                Solution::Todo
-            }
-
-            fn solve_part2_with(&self, input: &str) -> Solution {
-                Solution::Todo
-            }
-        }
-
-        #[cfg(test)]
-        mod tests {
-            use super::*;
+    let pretty_file = render_day(templates, year, day, &title, &comment_preamble)?;
+    tokio::fs::create_dir_all(output_path).await?;
 
-            #[test]
-            fn test_part1_example() {
-                assert_eq!(1, 1);
-            }
+    inputs::fetch_all(&accounts, http_config, year, day).await?;
+    tokio::fs::write(output_path.join("mod.rs"), pretty_file).await?;
 
-            #[test]
-            fn test_part1_real_input() {
-                let problem = #problem_ident {};
-                assert_eq!(problem.solve_part1(), Solution::Todo);
-            }
+    write_main(year, templates).await?;
+    fuzz::write_targets(year).await?;
 
-            #[test]
-            fn test_part2_example() {
-                assert_eq!(1, 1);
-            }
+    Ok(())
+}
 
-            #[test]
-            fn test_part2_real_input() {
-                let problem = #problem_ident {};
-                assert_eq!(problem.solve_part2(), Solution::Todo);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden::{assert_golden, fixture};
+
+    #[tokio::test]
+    async fn test_update_main() {
+        let src = std::env::temp_dir().join(format!("aoc-update-main-{}", std::process::id()));
+        let day = fixture("codegen/day06.rs");
+        let variant = variant::make_variant(&day, "Fast", format_ident!("Day06Fast")).unwrap();
+        for (module, source) in
+            [("day06", Some(&day)), ("day06fast", Some(&variant)), ("day10", None)]
+        {
+            std::fs::create_dir_all(src.join(module)).unwrap();
+            if let Some(source) = source {
+                std::fs::write(src.join(module).join("mod.rs"), source).unwrap();
             }
         }
-    };
+        // Only `day*` directories are days.
+        std::fs::create_dir_all(src.join("shared")).unwrap();
+        std::fs::write(src.join("day07.rs"), "").unwrap();
 
-    let pretty_file = prettyplease::unparse(&syn::parse_file(&final_doc.to_string())?);
-    tokio::fs::create_dir_all(output_path).await?;
+        let modules = day_modules(&src).await.unwrap();
+        let features = "#![feature(const_for)]\n";
+        let main = render_main(2023, modules, features, &Templates::default()).unwrap();
+        assert_golden("main.rs", &main);
 
-    tokio::fs::write(
-        output_path.join("input.txt"),
-        get_input(&client, year, day).await.unwrap_or_default(),
-    )
-    .await?;
-    tokio::fs::write(output_path.join("mod.rs"), pretty_file).await?;
+        std::fs::remove_dir_all(&src).unwrap();
+    }
 
-    write_main(year).await?;
+    #[test]
+    fn test_render_day() {
+        let description = question_comment(&fixture("codegen/description.md"));
+        let day = render_day(&Templates::default(), 2023, 6, "Day 6: Wait For It", &description);
+        assert_golden("day.rs", &day.unwrap());
+    }
 
-    Ok(())
+    #[test]
+    fn test_replace_description() {
+        let description = question_comment(&fixture("codegen/description.md"));
+        let replaced = replace_description(&fixture("codegen/day06.rs"), &description).unwrap();
+        assert_golden("only_desc.rs", &replaced);
+        // Running it again on its own output changes nothing.
+        assert_eq!(replace_description(&replaced, &description).unwrap(), replaced);
+        assert!(replace_description("struct Day06;", &description).is_err());
+    }
 }
@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use toml_edit::{Document, Value};
+use tracing::*;
+
+use crate::{templates::Templates, write_main};
+
+fn crate_manifest(year: u32) -> String {
+    format!(
+        r#"[package]
+name = "aoc{}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+anyhow = "1.0.75"
+common = {{ path = "../common" }}
+mimalloc = "0.1.39"
+
+[features]
+embed-inputs = []
+embed-encrypted-inputs = []
+"#,
+        year
+    )
+}
+
+/// Adds `member` to the workspace members, keeping the rest of the manifest as
+/// it was. Returns `None` if it's already there.
+pub fn add_workspace_member(manifest: &str, member: &str) -> anyhow::Result<Option<String>> {
+    let mut document = manifest.parse::<Document>()?;
+    let members = document["workspace"]["members"]
+        .as_array_mut()
+        .ok_or_else(|| anyhow::anyhow!("The workspace has no members list"))?;
+    if members.iter().any(|m| m.as_str() == Some(member)) {
+        return Ok(None);
+    }
+
+    // Match the existing one-member-per-line layout.
+    let multiline = members
+        .iter()
+        .any(|m| m.decor().prefix().and_then(|p| p.as_str()).is_some_and(|p| p.contains('\n')));
+    let mut value = Value::from(member);
+    if multiline {
+        value.decor_mut().set_prefix("\n    ");
+    } else if !members.is_empty() {
+        value.decor_mut().set_prefix(" ");
+    }
+    if let Some(last) = members.iter_mut().last() {
+        last.decor_mut().set_suffix("");
+    }
+    members.push_formatted(value);
+    if multiline {
+        members.set_trailing("\n");
+    }
+
+    Ok(Some(document.to_string()))
+}
+
+/// Creates `crates/aoc<year>` with an empty `main.rs` and adds it to the
+/// workspace. Anything that already exists is left alone.
+pub async fn new_year(year: u32, templates: &Templates) -> anyhow::Result<()> {
+    let crate_dir = format!("crates/aoc{}", year);
+    let crate_path = Path::new(&crate_dir);
+    tokio::fs::create_dir_all(crate_path.join("src")).await?;
+    tokio::fs::create_dir_all(format!("inputs/{}", year)).await?;
+
+    let manifest_path = crate_path.join("Cargo.toml");
+    if tokio::fs::try_exists(&manifest_path).await? {
+        info!("{:?} already exists", manifest_path);
+    } else {
+        tokio::fs::write(&manifest_path, crate_manifest(year)).await?;
+        info!("Wrote {:?}", manifest_path);
+    }
+
+    let workspace = tokio::fs::read_to_string("Cargo.toml").await?;
+    match add_workspace_member(&workspace, &crate_dir)? {
+        Some(updated) => {
+            tokio::fs::write("Cargo.toml", updated).await?;
+            info!("Added {} to the workspace", crate_dir);
+        }
+        None => info!("{} is already a workspace member", crate_dir),
+    }
+
+    if tokio::fs::try_exists(crate_path.join("src/main.rs")).await? {
+        info!("main.rs already exists, use only-main to regenerate it");
+    } else {
+        write_main(year, templates).await?;
+        info!("Wrote main.rs");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = r#"[workspace]
+resolver = "2"
+members = [
+    "crates/aoc-update",
+    "crates/aoc2023"
+]
+
+[profile.release]
+lto = true # keep this comment
+"#;
+
+    #[test]
+    fn test_add_workspace_member() {
+        let updated = add_workspace_member(WORKSPACE, "crates/aoc2024").unwrap().unwrap();
+        assert_eq!(
+            updated,
+            WORKSPACE
+                .replace("\"crates/aoc2023\"\n", "\"crates/aoc2023\",\n    \"crates/aoc2024\"\n")
+        );
+        assert_eq!(add_workspace_member(&updated, "crates/aoc2024").unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_workspace_member_inline() {
+        let manifest = "[workspace]\nmembers = [\"crates/common\"]\n";
+        let updated = add_workspace_member(manifest, "crates/aoc2024").unwrap().unwrap();
+        assert_eq!(updated, "[workspace]\nmembers = [\"crates/common\", \"crates/aoc2024\"]\n");
+        let document = updated.parse::<Document>().unwrap();
+        let members = document["workspace"]["members"].as_array().unwrap();
+        assert_eq!(
+            members.iter().filter_map(|m| m.as_str()).collect::<Vec<_>>(),
+            ["crates/common", "crates/aoc2024"]
+        );
+    }
+}
@@ -16,3 +16,16 @@ pub fn question_comment(comment: &str) -> TokenStream {
     let doc_comment: Vec<_> = buffer.lines().collect();
     quote! { #( #[doc = #doc_comment] )* }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden::{assert_golden, fixture};
+
+    #[test]
+    fn test_question_comment() {
+        let comment = question_comment(&fixture("codegen/description.md"));
+        let file = syn::parse2::<syn::File>(quote! { #comment pub struct Day06; }).unwrap();
+        assert_golden("question_comment.rs", &prettyplease::unparse(&file));
+    }
+}
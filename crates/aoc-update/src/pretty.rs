@@ -0,0 +1,180 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, ToTokens};
+
+/// Longest line `aoc_test!` settings are kept on, matching rustfmt.
+const MAX_WIDTH: usize = 100;
+
+pub fn is_aoc_test(item: &syn::ItemMacro) -> bool {
+    item.mac.path.segments.last().is_some_and(|s| s.ident == "aoc_test")
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(p) if p.as_char() == c)
+}
+
+/// Splits tokens on a punctuation character. Groups are single tokens, so
+/// only top-level separators count.
+fn split(tokens: Vec<TokenTree>, c: char) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![vec![]];
+    for token in tokens {
+        if is_punct(&token, c) {
+            parts.push(vec![]);
+        } else if let Some(part) = parts.last_mut() {
+            part.push(token);
+        }
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn expr(tokens: &[TokenTree]) -> Option<String> {
+    let expr = syn::parse2::<syn::Expr>(tokens.iter().cloned().collect()).ok()?;
+    let file: syn::File = syn::parse_quote!(const _: () = #expr;);
+    let printed = prettyplease::unparse(&file);
+    Some(printed.strip_prefix("const _: () = ")?.trim_end().strip_suffix(';')?.to_owned())
+}
+
+/// `input => expected`
+fn example(tokens: &[TokenTree]) -> Option<String> {
+    let arrow = tokens.windows(2).position(|w| is_punct(&w[0], '=') && is_punct(&w[1], '>'))?;
+    Some(format!("{} => {}", expr(&tokens[..arrow])?, expr(&tokens[arrow + 2..])?))
+}
+
+/// `part1: <expr>`, optionally followed by `, examples: [..]`.
+fn part(tokens: &[TokenTree]) -> Option<String> {
+    let [TokenTree::Ident(name), colon, rest @ ..] = tokens else {
+        return None;
+    };
+    if !is_punct(colon, ':') {
+        return None;
+    }
+    let examples_at = rest.windows(2).position(|w| {
+        is_punct(&w[0], ',') && matches!(&w[1], TokenTree::Ident(i) if i == "examples")
+    });
+    let Some(at) = examples_at else {
+        return Some(format!("    {}: {};", name, expr(rest)?));
+    };
+
+    let answer = expr(&rest[..at])?;
+    let [_, _, colon, TokenTree::Group(list)] = &rest[at..] else {
+        return None;
+    };
+    if !is_punct(colon, ':') {
+        return None;
+    }
+    let examples = split(list.stream().into_iter().collect(), ',')
+        .iter()
+        .map(|e| example(e))
+        .collect::<Option<Vec<_>>>()?;
+    let line = format!("    {}: {}, examples: [{}];", name, answer, examples.join(", "));
+    if line.len() <= MAX_WIDTH && !line.contains('\n') {
+        return Some(line);
+    }
+    let entries = examples.iter().map(|e| format!("        {},\n", e)).collect::<Vec<_>>();
+    Some(format!("    {}: {}, examples: [\n{}    ];", name, answer, entries.concat()))
+}
+
+/// Lays out an `aoc_test!` invocation with one setting per line, and any extra
+/// tests formatted as normal functions. `None` if it doesn't look as expected.
+fn format_aoc_test(item: &syn::ItemMacro) -> Option<String> {
+    let path = item.mac.path.to_token_stream().to_string().replace(' ', "");
+    let tokens = item.mac.tokens.clone().into_iter().collect::<Vec<_>>();
+    let tests_at = tokens.iter().position(|t| is_punct(t, '#')).unwrap_or(tokens.len());
+
+    let mut sections = split(tokens[..tests_at].to_vec(), ';').into_iter();
+    let mut lines = vec![format!("    {};", expr(&sections.next()?)?)];
+    for section in sections {
+        match section.first() {
+            Some(TokenTree::Ident(name)) if name == "variants" => {
+                let [_, colon, TokenTree::Group(list)] = section.as_slice() else {
+                    return None;
+                };
+                if !is_punct(colon, ':') {
+                    return None;
+                }
+                let variants = split(list.stream().into_iter().collect(), ',')
+                    .iter()
+                    .map(|v| expr(v))
+                    .collect::<Option<Vec<_>>>()?;
+                lines.push(format!("    variants: [{}];", variants.join(", ")));
+            }
+            _ => lines.push(part(&section)?),
+        }
+    }
+
+    let tests = tokens[tests_at..].iter().cloned().collect::<TokenStream>();
+    let tests = syn::parse2::<syn::File>(tests).ok()?;
+    for test in tests.items {
+        let printed =
+            prettyplease::unparse(&syn::File { shebang: None, attrs: vec![], items: vec![test] });
+        lines.push(String::new());
+        lines.extend(printed.lines().map(|l| format!("    {}", l).trim_end().to_owned()));
+    }
+
+    Some(format!("{}! {{\n{}\n}}\n", path, lines.join("\n")))
+}
+
+/// Pretty-prints a day like `prettyplease::unparse`, except `aoc_test!`
+/// invocations are laid out properly. Neither prettyplease nor rustfmt formats
+/// macro bodies, so they'd otherwise end up on a single line.
+pub fn unparse(file: &syn::File) -> String {
+    let mut file = file.clone();
+    let mut formatted = vec![];
+    for item in file.items.iter_mut() {
+        let syn::Item::Macro(mac) = item else {
+            continue;
+        };
+        if !is_aoc_test(mac) || !mac.attrs.is_empty() {
+            continue;
+        }
+        if let Some(layout) = format_aoc_test(mac) {
+            let marker = format_ident!("__AOC_TEST_{}", formatted.len());
+            formatted.push((format!("const {}: () = ();\n", marker), layout));
+            *item = syn::parse_quote!(const #marker: () = (););
+        }
+    }
+
+    let mut printed = prettyplease::unparse(&file);
+    for (marker, layout) in formatted {
+        printed = printed.replace(&marker, &layout);
+    }
+    printed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unparse_aoc_test() {
+        let file = syn::parse_file(
+            r#"
+const EXAMPLE: &str = "LR";
+common::aoc_test! { Day08 {}; part1 : Solution::U16(18827), examples: [EXAMPLE => Solution::U16(2), "LLR\n\nAAA = (BBB, BBB)" => Solution::U16(6),]; part2: Solution::Todo; variants: [crate::day08alt::Day08Alt {}];
+    #[test] fn test_offset() { let problem = Day08 {}; assert_eq!(problem.solve_part2_with("L"), Solution::U64(4)); }
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            unparse(&file),
+            r#"const EXAMPLE: &str = "LR";
+common::aoc_test! {
+    Day08 {};
+    part1: Solution::U16(18827), examples: [
+        EXAMPLE => Solution::U16(2),
+        "LLR\n\nAAA = (BBB, BBB)" => Solution::U16(6),
+    ];
+    part2: Solution::Todo;
+    variants: [crate::day08alt::Day08Alt {}];
+
+    #[test]
+    fn test_offset() {
+        let problem = Day08 {};
+        assert_eq!(problem.solve_part2_with("L"), Solution::U64(4));
+    }
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use tracing::*;
+
+use crate::pretty;
+
+const DEFAULT_DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const DEFAULT_MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+
+/// Templates for generated files. Either can be overridden by putting
+/// `day.rs.tmpl` or `main.rs.tmpl` in the templates directory.
+///
+/// Placeholders are written as `{{name}}`. The day template gets `year`, `day`,
+/// `module` (e.g. `day01`), `struct` (e.g. `Day01`), `title` (a string literal)
+/// and `description` (doc attributes, to put before the struct). The main
+/// template gets `year`, `modules` (the `mod` items), `uses` (the glob imports)
+/// and `problems` (the boxed problems, comma separated).
+#[derive(Clone, Debug)]
+pub struct Templates {
+    pub day: String,
+    pub main: String,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self { day: DEFAULT_DAY_TEMPLATE.to_owned(), main: DEFAULT_MAIN_TEMPLATE.to_owned() }
+    }
+}
+
+async fn load_template(dir: &Path, name: &str, default: &str) -> anyhow::Result<String> {
+    let path = dir.join(name);
+    match tokio::fs::read_to_string(&path).await {
+        Ok(template) => {
+            info!("Using template {:?}", path);
+            Ok(template)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(default.to_owned()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl Templates {
+    pub async fn load(dir: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            day: load_template(dir, "day.rs.tmpl", DEFAULT_DAY_TEMPLATE).await?,
+            main: load_template(dir, "main.rs.tmpl", DEFAULT_MAIN_TEMPLATE).await?,
+        })
+    }
+}
+
+/// Fills in a template's placeholders and pretty-prints the result, failing if
+/// a placeholder is unknown or the result isn't valid Rust. The template is
+/// scanned once, so values are never searched for placeholders themselves; a
+/// description can contain `{{` without being mistaken for one.
+pub fn render(template: &str, values: &[(&str, String)]) -> anyhow::Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}");
+        let value = end.and_then(|end| values.iter().find(|(name, _)| *name == &after[..end]));
+        let (Some(end), Some((_, value))) = (end, value) else {
+            let placeholder = after.split("}}").next().unwrap_or_default();
+            anyhow::bail!("Unknown placeholder {{{{{}}}}} in template", placeholder);
+        };
+        rendered.push_str(value);
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(pretty::unparse(&syn::parse_file(&rendered)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            "pub struct {{struct}};\nconst YEAR: u32 = {{year}};",
+            &[("struct", "Day01".to_owned()), ("year", "2023".to_owned())],
+        )
+        .unwrap();
+        assert_eq!(rendered, "pub struct Day01;\nconst YEAR: u32 = 2023;\n");
+
+        let error = render("pub struct {{name}};", &[]).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown placeholder {{name}} in template");
+        assert!(render("pub struct {{struct}}", &[("struct", "Day01".to_owned())]).is_err());
+
+        // Values are inserted as they are, even if they look like placeholders.
+        let rendered = render(
+            "{{description}}\npub struct {{struct}};",
+            &[
+                ("description", "/// `{{<!>},{<!>}}` and {{struct}}".to_owned()),
+                ("struct", "Day09".to_owned()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(rendered, "/// `{{<!>},{<!>}}` and {{struct}}\npub struct Day09;\n");
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::Path;
+
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::{format_ident, ToTokens};
+use syn::visit_mut::VisitMut;
+use tracing::*;
+
+use crate::{fuzz, pretty, templates::Templates, write_main};
+
+fn is_problem_impl(item: &syn::ItemImpl) -> bool {
+    item.trait_
+        .as_ref()
+        .is_some_and(|(_, path, _)| path.segments.last().is_some_and(|s| s.ident == "Problem"))
+}
+
+/// Finds the type a day module implements `Problem` for.
+pub fn problem_struct(file: &syn::File) -> Option<Ident> {
+    file.items.iter().find_map(|item| match item {
+        syn::Item::Impl(item) if is_problem_impl(item) => match item.self_ty.as_ref() {
+            syn::Type::Path(ty) => ty.path.get_ident().cloned(),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+struct Rename {
+    from: Ident,
+    to: Ident,
+}
+
+impl Rename {
+    fn rename_tokens(&self, tokens: TokenStream) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Ident(ident) if ident == self.from => TokenTree::Ident(self.to.clone()),
+                TokenTree::Group(group) => {
+                    let mut renamed =
+                        Group::new(group.delimiter(), self.rename_tokens(group.stream()));
+                    renamed.set_span(group.span());
+                    TokenTree::Group(renamed)
+                }
+                token => token,
+            })
+            .collect()
+    }
+}
+
+impl VisitMut for Rename {
+    fn visit_ident_mut(&mut self, ident: &mut Ident) {
+        if *ident == self.from {
+            *ident = self.to.clone();
+        }
+    }
+
+    // Macro arguments are plain tokens, so `assert_eq!(Day01 {}.solve_part1(), ..)`
+    // wouldn't be renamed otherwise.
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        syn::visit_mut::visit_macro_mut(self, mac);
+        mac.tokens = self.rename_tokens(std::mem::take(&mut mac.tokens));
+    }
+}
+
+/// Points `include_str!`s with relative paths, like a day's examples, back at
+/// the original day's directory from the variant's.
+struct RebaseIncludes {
+    module: String,
+}
+
+impl VisitMut for RebaseIncludes {
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        let is_include = mac.path.is_ident("include_str") || mac.path.is_ident("include_bytes");
+        let Ok(path) = syn::parse2::<syn::LitStr>(mac.tokens.clone()) else {
+            return;
+        };
+        if is_include && !path.value().starts_with('/') {
+            let rebased = format!("../{}/{}", self.module, path.value());
+            mac.tokens = syn::LitStr::new(&rebased, path.span()).into_token_stream();
+        }
+    }
+}
+
+/// Drops `variants: [..];` from a day's `aoc_test!`, since a copy of the day
+/// shouldn't be checked against the day's other variants, itself included.
+fn drop_variants(tokens: TokenStream) -> TokenStream {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut kept = TokenStream::new();
+    while let Some(token) = tokens.next() {
+        match &token {
+            TokenTree::Ident(ident) if ident == "variants" => {
+                // Skip the `:`, the list and the `;`.
+                tokens.by_ref().take(3).for_each(drop);
+            }
+            _ => kept.extend([token]),
+        }
+    }
+    kept
+}
+
+/// Turns a day's module into a variant of it: the struct is renamed, `name`
+/// gets the variant appended and `variant` is implemented. Everything else,
+/// including the input, description and tests, is kept.
+pub fn make_variant(source: &str, variant: &str, struct_ident: Ident) -> anyhow::Result<String> {
+    let mut file = syn::parse_file(source)?;
+    let original =
+        problem_struct(&file).ok_or_else(|| anyhow::anyhow!("No `impl Problem` found"))?;
+    RebaseIncludes { module: original.to_string().to_lowercase() }.visit_file_mut(&mut file);
+    Rename { from: original, to: struct_ident }.visit_file_mut(&mut file);
+    for item in file.items.iter_mut() {
+        if let syn::Item::Macro(m) = item {
+            if pretty::is_aoc_test(m) {
+                m.mac.tokens = drop_variants(std::mem::take(&mut m.mac.tokens));
+            }
+        }
+    }
+
+    let problem_impl = file
+        .items
+        .iter_mut()
+        .find_map(|item| match item {
+            syn::Item::Impl(item) if is_problem_impl(item) => Some(item),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("No `impl Problem` found"))?;
+
+    problem_impl
+        .items
+        .retain(|item| !matches!(item, syn::ImplItem::Fn(f) if f.sig.ident == "variant"));
+    for item in problem_impl.items.iter_mut() {
+        if let syn::ImplItem::Fn(f) = item {
+            if f.sig.ident == "name" {
+                let name = match f.block.stmts.as_slice() {
+                    [syn::Stmt::Expr(
+                        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }),
+                        None,
+                    )] => s.value(),
+                    _ => anyhow::bail!("Expected `name` to return a string literal"),
+                };
+                let name = format!("{} ({})", name, variant);
+                f.block = syn::parse_quote!({ #name });
+            }
+        }
+    }
+    problem_impl.items.push(syn::parse_quote! {
+        fn variant(&self) -> Option<&str> {
+            Some(#variant)
+        }
+    });
+
+    Ok(pretty::unparse(&file))
+}
+
+/// Module and struct names for a variant, e.g. `day01alt` and `Day01Alt` for
+/// day 1's `alt` variant.
+pub fn variant_names(day: u8, name: &str) -> anyhow::Result<(String, Ident, String)> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        anyhow::bail!("Variant names must be letters and digits, got {:?}", name);
+    }
+    let mut chars = name.chars();
+    let title =
+        chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect::<String>();
+
+    Ok((
+        format!("day{:02}{}", day, name.to_ascii_lowercase()),
+        format_ident!("Day{:02}{}", day, title),
+        title,
+    ))
+}
+
+/// Creates `dayNN<name>` as a copy of `dayNN` and registers it.
+pub async fn new_variant(
+    templates: &Templates,
+    year: u32,
+    day: u8,
+    name: &str,
+) -> anyhow::Result<()> {
+    let (module, struct_ident, title) = variant_names(day, name)?;
+    let src = format!("crates/aoc{}/src", year);
+    let source_path = Path::new(&src).join(format!("day{:02}", day)).join("mod.rs");
+    let target_dir = Path::new(&src).join(&module);
+    if tokio::fs::try_exists(&target_dir).await? {
+        anyhow::bail!("{:?} already exists", target_dir);
+    }
+
+    let source = tokio::fs::read_to_string(&source_path).await?;
+    let variant = make_variant(&source, &title, struct_ident.clone())?;
+    tokio::fs::create_dir_all(&target_dir).await?;
+    tokio::fs::write(target_dir.join("mod.rs"), variant).await?;
+    info!("Created {} as {}", module, struct_ident);
+
+    write_main(year, templates).await?;
+    fuzz::write_targets(year).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: &str = r#"
+use common::{Problem, Solution};
+
+/// --- Day 1: Trebuchet?! ---
+pub struct Day01;
+
+const EXAMPLE: &str = include_str!("examples/part1_a.txt");
+
+impl Day01 {
+    fn parse(input: &str) -> u32 {
+        input.len() as u32
+    }
+}
+
+impl Problem for Day01 {
+    fn problem_input(&self) -> &'static str {
+        common::problem_input!(2023, day01)
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "Day 1: Trebuchet?!"
+    }
+
+    fn solve_part1_with(&self, input: &str) -> Solution {
+        Day01::parse(input).into()
+    }
+
+    fn solve_part2_with(&self, input: &str) -> Solution {
+        Solution::Todo
+    }
+}
+
+common::aoc_test! {
+    Day01 {};
+    part1: Solution::U32(1), examples: ["1abc2" => Solution::U32(5)];
+    part2: Solution::Todo;
+    variants: [crate::day01simd::Day01Simd {}];
+
+    #[test]
+    fn test_parse() {
+        let problem = Day01 {};
+        assert_eq!(problem.solve_part1_with("ab"), Solution::U32(2));
+    }
+}
+"#;
+
+    #[test]
+    fn test_make_variant_golden() {
+        let day = crate::golden::fixture("codegen/day06.rs");
+        let variant = make_variant(&day, "Fast", format_ident!("Day06Fast")).unwrap();
+        crate::golden::assert_golden("variant.rs", &variant);
+    }
+
+    #[test]
+    fn test_variant_names() {
+        let (module, ident, title) = variant_names(1, "alt").unwrap();
+        assert_eq!(
+            (module.as_str(), ident.to_string(), title.as_str()),
+            ("day01alt", "Day01Alt".to_owned(), "Alt")
+        );
+        let (module, ident, _) = variant_names(12, "Simd2").unwrap();
+        assert_eq!((module.as_str(), ident.to_string()), ("day12simd2", "Day12Simd2".to_owned()));
+        assert!(variant_names(1, "fast-parse").is_err());
+    }
+
+    #[test]
+    fn test_make_variant() {
+        let variant = make_variant(DAY, "Alt", format_ident!("Day01Alt")).unwrap();
+        let file = syn::parse_file(&variant).unwrap();
+        assert_eq!(problem_struct(&file).unwrap(), "Day01Alt");
+        assert!(!variant.contains("Day01 "));
+        assert!(variant.contains("Day01Alt::parse(input)"));
+        assert!(variant.contains("common::aoc_test! {\n    Day01Alt {};\n    part1:"));
+        assert!(variant.contains("let problem = Day01Alt {};"));
+        assert!(!variant.contains("variants:"));
+        assert!(variant.contains("include_str!(\"../day01/examples/part1_a.txt\")"));
+        assert!(variant.contains("common::problem_input!(2023, day01)"));
+        assert!(variant.contains("\"Day 1: Trebuchet?! (Alt)\""));
+        assert!(variant.contains("fn variant(&self) -> Option<&str> {\n        Some(\"Alt\")"));
+        assert!(variant.contains("/// --- Day 1: Trebuchet?! ---"));
+    }
+}
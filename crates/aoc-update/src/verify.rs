@@ -0,0 +1,101 @@
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tracing::*;
+
+use crate::{src_dir, templates::Templates, update_main};
+
+/// Every year with a `crates/aocYYYY` crate.
+async fn years() -> anyhow::Result<Vec<u32>> {
+    let mut entries = tokio::fs::read_dir("crates").await?;
+    let mut years = vec![];
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some(year) = name.strip_prefix("aoc").and_then(|y| y.parse::<u32>().ok()) {
+            years.push(year);
+        }
+    }
+    years.sort();
+    Ok(years)
+}
+
+/// Formats `source` the way `cargo fmt` does after files are generated, using
+/// the workspace's `rustfmt.toml`.
+async fn rustfmt(source: &str) -> anyhow::Result<String> {
+    let mut child = tokio::process::Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("No stdin for rustfmt"))?;
+    stdin.write_all(source.as_bytes()).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        anyhow::bail!("rustfmt failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The changes regenerating a year's `main.rs` would make, as a unified diff.
+/// `None` if it's up to date.
+async fn stale_main(year: u32, templates: &Templates) -> anyhow::Result<Option<String>> {
+    let path = src_dir(year).join("main.rs");
+    let on_disk = match tokio::fs::read_to_string(&path).await {
+        Ok(main) => main,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let generated = rustfmt(&update_main(year, templates).await?).await?;
+    if generated == on_disk {
+        return Ok(None);
+    }
+
+    let name = path.to_string_lossy();
+    let patch = diffy::create_patch(&on_disk, &generated).to_string();
+    Ok(Some(patch.replacen("--- original", &format!("--- {}", name), 1).replacen(
+        "+++ modified",
+        &format!("+++ {} (regenerated)", name),
+        1,
+    )))
+}
+
+/// Checks `main.rs` is up to date for a year, or every year with `all`,
+/// printing what regenerating would change. Fails if any are stale.
+pub async fn run(templates: &Templates, target: &str) -> anyhow::Result<()> {
+    let years = match target {
+        "all" => years().await?,
+        year => vec![year.parse()?],
+    };
+
+    let mut stale = vec![];
+    for year in years {
+        match stale_main(year, templates).await? {
+            Some(diff) => {
+                print!("{}", diff);
+                stale.push(year);
+            }
+            None => info!(year, "main.rs is up to date"),
+        }
+    }
+
+    if !stale.is_empty() {
+        let years = stale.iter().map(|y| y.to_string()).collect::<Vec<_>>().join(", ");
+        anyhow::bail!("main.rs is out of date for {}, run `make update-main YEAR=<year>`", years);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rustfmt() {
+        let formatted = rustfmt("fn main(){let x=vec![1,2];}").await.unwrap();
+        assert_eq!(formatted, "fn main() {\n    let x = vec![1, 2];\n}\n");
+        assert!(rustfmt("fn main( {").await.is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use std::{io::Write, time::Duration};
+
+use common::progress::days_in_event;
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+use tracing::*;
+
+use crate::{accounts::NotFound, http::HttpConfig, templates::Templates, update_day};
+
+/// Puzzles unlock at midnight US Eastern time, which is UTC-5 in December.
+pub const UNLOCK_OFFSET: UtcOffset = match UtcOffset::from_hms(-5, 0, 0) {
+    Ok(offset) => offset,
+    Err(_) => panic!("Invalid unlock offset"),
+};
+
+/// How many times to retry after the unlock instant before giving up, in case
+/// our clock runs fast or the site is slow to publish.
+const UNLOCK_RETRIES: u32 = 6;
+const UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+pub fn unlock_time(year: u32, day: u8) -> anyhow::Result<OffsetDateTime> {
+    let days = days_in_event(year);
+    if !(1..=days).contains(&day) {
+        anyhow::bail!(
+            "There is no day {} in {}, puzzles run from the 1st to the {}th",
+            day,
+            year,
+            days
+        );
+    }
+    let date = Date::from_calendar_date(year as i32, Month::December, day)?;
+    Ok(date.midnight().assume_offset(UNLOCK_OFFSET))
+}
+
+/// The puzzle that unlocked today, or the one unlocking within the hour if
+/// it's nearly midnight. Returns `None` outside the event.
+pub fn todays_puzzle(now: OffsetDateTime) -> Option<(u32, u8)> {
+    let eastern = (now + time::Duration::HOUR).to_offset(UNLOCK_OFFSET);
+    let year = eastern.year() as u32;
+    (eastern.month() == Month::December && eastern.day() <= days_in_event(year))
+        .then(|| (year, eastern.day()))
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+async fn countdown(unlock: OffsetDateTime) -> anyhow::Result<()> {
+    loop {
+        let remaining = unlock - OffsetDateTime::now_utc();
+        if !remaining.is_positive() {
+            eprintln!();
+            return Ok(());
+        }
+        let remaining = Duration::try_from(remaining)?;
+        eprint!("\rUnlocks in {}", format_remaining(remaining));
+        std::io::stderr().flush()?;
+        tokio::time::sleep(remaining.min(Duration::from_secs(1))).await;
+    }
+}
+
+/// Whether fetching failed because the puzzle isn't published yet. Anything
+/// else, like an expired token or the site being down, won't be fixed by
+/// trying again in a few seconds.
+fn still_locked(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<NotFound>().is_some()
+}
+
+/// Waits for a puzzle to unlock, then scaffolds it and fetches its inputs.
+pub async fn wait(
+    http_config: &HttpConfig,
+    templates: &Templates,
+    year: u32,
+    day: u8,
+) -> anyhow::Result<()> {
+    let unlock = unlock_time(year, day)?;
+    info!(year, day, "Puzzle unlocks at {}", unlock);
+    countdown(unlock).await?;
+
+    let mut attempt = 0;
+    loop {
+        match update_day(http_config, templates, year, day, None).await {
+            Ok(()) => return Ok(()),
+            Err(e) if still_locked(&e) && attempt < UNLOCK_RETRIES => {
+                warn!("Puzzle isn't available yet ({}), retrying in {:?}", e, UNLOCK_RETRY_DELAY);
+                tokio::time::sleep(UNLOCK_RETRY_DELAY).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+pub async fn today(http_config: &HttpConfig, templates: &Templates) -> anyhow::Result<()> {
+    let (year, day) = todays_puzzle(OffsetDateTime::now_utc())
+        .ok_or_else(|| anyhow::anyhow!("No puzzle unlocks today, try `aoc-update wait`"))?;
+    wait(http_config, templates, year, day).await
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::accounts::TokenError;
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(unlock_time(2023, 8).unwrap(), datetime!(2023-12-08 05:00 UTC));
+        assert!(unlock_time(2023, 26).is_err());
+        assert_eq!(unlock_time(2025, 12).unwrap(), datetime!(2025-12-12 05:00 UTC));
+        assert!(unlock_time(2025, 13).is_err());
+    }
+
+    #[test]
+    fn test_todays_puzzle() {
+        assert_eq!(todays_puzzle(datetime!(2023-12-08 12:00 UTC)), Some((2023, 8)));
+        // Still the 7th in Eastern time, but the 8th unlocks within the hour.
+        assert_eq!(todays_puzzle(datetime!(2023-12-08 04:30 UTC)), Some((2023, 8)));
+        assert_eq!(todays_puzzle(datetime!(2023-12-08 03:00 UTC)), Some((2023, 7)));
+        assert_eq!(todays_puzzle(datetime!(2023-12-01 04:30 UTC)), Some((2023, 1)));
+        assert_eq!(todays_puzzle(datetime!(2023-12-26 12:00 UTC)), None);
+        assert_eq!(todays_puzzle(datetime!(2025-12-12 12:00 UTC)), Some((2025, 12)));
+        assert_eq!(todays_puzzle(datetime!(2025-12-13 12:00 UTC)), None);
+    }
+
+    #[test]
+    fn test_still_locked() {
+        let url = "https://adventofcode.com/2023/day/8".to_owned();
+        assert!(still_locked(&NotFound(url).into()));
+        assert!(!still_locked(&TokenError::Expired("default".to_owned()).into()));
+        assert!(!still_locked(&anyhow::anyhow!("503 Service Unavailable")));
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(3 * 3600 + 61)), "03:01:01");
+    }
+}
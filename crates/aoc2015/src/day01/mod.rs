@@ -51,7 +51,7 @@ use common::{Problem, Solution};
 pub struct Day01;
 impl Problem for Day01 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2015, day01)
     }
     fn day(&self) -> u8 {
         1u8
@@ -84,36 +84,21 @@ impl Problem for Day01 {
         Solution::Error
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part1_with("(())"), 0.into());
-        assert_eq!(problem.solve_part1_with("()()"), 0.into());
-        assert_eq!(problem.solve_part1_with("((("), 3.into());
-        assert_eq!(problem.solve_part1_with("(()(()("), 3.into());
-        assert_eq!(problem.solve_part1_with("))((((("), 3.into());
-        assert_eq!(problem.solve_part1_with("())"), (-1).into());
-        assert_eq!(problem.solve_part1_with("))("), (-1).into());
-        assert_eq!(problem.solve_part1_with(")))"), (-3).into());
-        assert_eq!(problem.solve_part1_with(")())())"), (-3).into());
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part1(), 232.into());
-    }
-    #[test]
-    fn test_part2_example() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part2_with(")"), Solution::USize(1));
-        assert_eq!(problem.solve_part2_with("()())"), Solution::USize(5));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part2(), Solution::USize(1783));
-    }
+common::aoc_test! {
+    Day01 {};
+    part1: 232.into(), examples: [
+        "(())" => 0.into(),
+        "()()" => 0.into(),
+        "(((" => 3.into(),
+        "(()(()(" => 3.into(),
+        "))(((((" => 3.into(),
+        "())" => (-1).into(),
+        "))(" => (-1).into(),
+        ")))" => (-3).into(),
+        ")())())" => (-3).into(),
+    ];
+    part2: Solution::USize(1783), examples: [
+        ")" => Solution::USize(1),
+        "()())" => Solution::USize(5),
+    ];
 }
@@ -47,7 +47,7 @@ use common::{Problem, Solution};
 pub struct Day02;
 impl Problem for Day02 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2015, day02)
     }
     fn day(&self) -> u8 {
         2u8
@@ -84,29 +84,14 @@ impl Problem for Day02 {
         Solution::U32(total)
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part1_with("2x3x4"), Solution::U32(58));
-        assert_eq!(problem.solve_part1_with("1x1x10"), Solution::U32(43));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part1(), Solution::U32(1606483));
-    }
-    #[test]
-    fn test_part2_example() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part2_with("2x3x4"), Solution::U32(34));
-        assert_eq!(problem.solve_part2_with("1x1x10"), Solution::U32(14));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part2(), Solution::U32(3842356));
-    }
+common::aoc_test! {
+    Day02 {};
+    part1: Solution::U32(1606483), examples: [
+        "2x3x4" => Solution::U32(58),
+        "1x1x10" => Solution::U32(43),
+    ];
+    part2: Solution::U32(3842356), examples: [
+        "2x3x4" => Solution::U32(34),
+        "1x1x10" => Solution::U32(14),
+    ];
 }
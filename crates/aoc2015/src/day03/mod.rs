@@ -55,7 +55,7 @@ const fn into_pos(x: i8, y: i8) -> usize {
 
 impl Problem for Day03 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2015, day03)
     }
     fn day(&self) -> u8 {
         3u8
@@ -114,31 +114,16 @@ impl Problem for Day03 {
         Solution::USize(deliveries.iter().filter(|&d| *d > 0).count())
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part1_with(">"), Solution::USize(2));
-        assert_eq!(problem.solve_part1_with("^>v<"), Solution::USize(4));
-        assert_eq!(problem.solve_part1_with("^v^v^v^v^v"), Solution::USize(2));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part1(), Solution::USize(2565));
-    }
-    #[test]
-    fn test_part2_example() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part2_with("^v"), Solution::USize(3));
-        assert_eq!(problem.solve_part2_with("^>v<"), Solution::USize(3));
-        assert_eq!(problem.solve_part2_with("^v^v^v^v^v"), Solution::USize(11));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part2(), Solution::USize(2639));
-    }
+common::aoc_test! {
+    Day03 {};
+    part1: Solution::USize(2565), examples: [
+        ">" => Solution::USize(2),
+        "^>v<" => Solution::USize(4),
+        "^v^v^v^v^v" => Solution::USize(2),
+    ];
+    part2: Solution::USize(2639), examples: [
+        "^v" => Solution::USize(3),
+        "^>v<" => Solution::USize(3),
+        "^v^v^v^v^v" => Solution::USize(11),
+    ];
 }
@@ -102,7 +102,7 @@ fn parse(data: &str) -> Vec<u32> {
 }
 impl Problem for Day01 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day01)
     }
     fn day(&self) -> u8 {
         1
@@ -159,7 +159,7 @@ impl Day02 {
 }
 impl Problem for Day02 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day02)
     }
     fn day(&self) -> u8 {
         2
@@ -171,7 +171,7 @@ impl Day03 {
 
 impl Problem for Day03 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day03)
     }
     fn day(&self) -> u8 {
         3u8
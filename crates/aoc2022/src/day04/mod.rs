@@ -154,7 +154,7 @@ impl Day04 {
 
 impl Problem for Day04 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day04)
     }
     fn day(&self) -> u8 {
         4u8
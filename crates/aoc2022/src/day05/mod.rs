@@ -1,7 +1,10 @@
 use std::collections::VecDeque;
 
 use anyhow::Context;
-use common::{Problem, Solution};
+use common::{
+    generator::{InputGenerator, Rng},
+    Problem, Solution,
+};
 /// \--- Day 5: Supply Stacks ---
 /// ----------
 ///
@@ -270,9 +273,54 @@ impl Day05 {
     }
 }
 
+impl InputGenerator for Day05 {
+    /// Up to nine stacks holding `3 * size` crates, then `5 * size` moves that
+    /// never take more crates than a stack holds.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let columns = rng.between(2, 9) as usize;
+        let mut stacks = vec![vec![]; columns];
+        for _ in 0..size * 3 {
+            stacks[rng.index(columns)].push((b'A' + rng.below(26) as u8) as char);
+        }
+
+        let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                let row = stacks
+                    .iter()
+                    .map(|s| s.get(level).map_or("   ".to_owned(), |c| format!("[{}]", c)))
+                    .collect::<Vec<_>>();
+                row.join(" ").trim_end().to_owned()
+            })
+            .collect::<Vec<_>>();
+        lines.push((1..=columns).map(|c| format!(" {} ", c)).collect::<Vec<_>>().join(" "));
+        lines.push(String::new());
+
+        for _ in 0..size * 5 {
+            let Some(from) = (0..columns)
+                .cycle()
+                .skip(rng.index(columns))
+                .take(columns)
+                .find(|&c| !stacks[c].is_empty())
+            else {
+                break;
+            };
+            let to = (from + 1 + rng.index(columns - 1)) % columns;
+            let amount = rng.between(1, stacks[from].len() as u64) as usize;
+            let split = stacks[from].len() - amount;
+            let moved = stacks[from].split_off(split);
+            stacks[to].extend(moved);
+            lines.push(format!("move {} from {} to {}", amount, from + 1, to + 1));
+        }
+
+        lines.join("\n")
+    }
+}
+
 impl Problem for Day05 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day05)
     }
     fn day(&self) -> u8 {
         5u8
@@ -280,6 +328,9 @@ impl Problem for Day05 {
     fn name(&self) -> &str {
         "Day 5: Supply Stacks"
     }
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let crane_game = self.parse(input, false).unwrap();
         Solution::Str(crane_game.get_top_crates().unwrap_or_else(|_| "failed".into()))
@@ -289,3 +340,22 @@ impl Problem for Day05 {
         Solution::Str(crane_game.get_top_crates().unwrap_or_else(|_| "failed".into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generator::check_property;
+    #[test]
+    fn test_generated_inputs() {
+        // Both cranes move the same number of crates, just in a different order.
+        let problem = Day05 {};
+        check_property(&problem, 100, 20, |input| {
+            match (problem.solve_part1_with(input), problem.solve_part2_with(input)) {
+                (Solution::Str(part1), Solution::Str(part2)) => {
+                    part1.len() == part2.len() && part1.chars().all(|c| c.is_ascii_uppercase())
+                }
+                _ => false,
+            }
+        });
+    }
+}
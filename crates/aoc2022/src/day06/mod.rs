@@ -105,7 +105,7 @@ impl Day06 {
 
 impl Problem for Day06 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day06)
     }
     fn day(&self) -> u8 {
         6u8
@@ -6,7 +6,10 @@ use std::{
 
 use anyhow::{anyhow, Context, Result};
 
-use common::{Problem, Solution};
+use common::{
+    generator::{InputGenerator, Rng},
+    Problem, Solution,
+};
 /// \--- Day 7: No Space Left On Device ---
 /// ----------
 ///
@@ -322,9 +325,59 @@ impl Day07 {
     }
 }
 
+/// A directory in a generated filesystem, as indices into a flat list.
+struct GeneratedDir {
+    name: String,
+    files: Vec<u64>,
+    children: Vec<usize>,
+}
+
+impl Day07 {
+    fn write_transcript(dirs: &[GeneratedDir], idx: usize, lines: &mut Vec<String>) {
+        lines.push("$ ls".to_owned());
+        for &child in dirs[idx].children.iter() {
+            lines.push(format!("dir {}", dirs[child].name));
+        }
+        for (n, size) in dirs[idx].files.iter().enumerate() {
+            lines.push(format!("{} file{}.dat", size, n));
+        }
+        for &child in dirs[idx].children.iter() {
+            lines.push(format!("$ cd {}", dirs[child].name));
+            Self::write_transcript(dirs, child, lines);
+            lines.push("$ cd ..".to_owned());
+        }
+    }
+}
+
+impl InputGenerator for Day07 {
+    /// A tree of `size` directories below `/`, explored depth first. The root
+    /// gets one extra file so the disk is between 40M and 70M full, leaving
+    /// part 2 something to free.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let max_file = (10_000_000 / (size as u64 * 3 + 1)).clamp(1, 300_000);
+        let mut dirs = vec![GeneratedDir { name: "/".to_owned(), files: vec![], children: vec![] }];
+        for idx in 1..=size {
+            let parent = rng.index(dirs.len());
+            dirs[parent].children.push(idx);
+            // Names are unique across the whole tree, which keeps them unique
+            // within each directory too.
+            let name = format!("d{}", idx);
+            let files = (0..rng.below(6)).map(|_| rng.between(1, max_file)).collect();
+            dirs.push(GeneratedDir { name, files, children: vec![] });
+        }
+
+        let used = dirs.iter().flat_map(|d| d.files.iter()).sum::<u64>();
+        dirs[0].files.push(rng.between(40_000_001, 69_000_000) - used);
+
+        let mut lines = vec!["$ cd /".to_owned()];
+        Self::write_transcript(&dirs, 0, &mut lines);
+        lines.join("\n")
+    }
+}
+
 impl Problem for Day07 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day07)
     }
     fn day(&self) -> u8 {
         7u8
@@ -332,6 +385,9 @@ impl Problem for Day07 {
     fn name(&self) -> &str {
         "Day 7: No Space Left On Device"
     }
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         Solution::U64(self.solve_actual(&self.parse(input)))
     }
@@ -339,3 +395,24 @@ impl Problem for Day07 {
         Solution::U64(self.solve_actual_part2(&self.parse(input)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generator::check_property;
+    #[test]
+    fn test_generated_inputs() {
+        // The directory to delete frees enough space, and is never bigger than
+        // the root.
+        let problem = Day07 {};
+        check_property(&problem, 100, 50, |input| {
+            let used =
+                input.lines().filter_map(|l| l.split_once(' ')?.0.parse::<u64>().ok()).sum::<u64>();
+            let needed = 30_000_000 - (70_000_000 - used);
+            match problem.solve_part2_with(input) {
+                Solution::U64(freed) => freed >= needed && freed <= used,
+                _ => false,
+            }
+        });
+    }
+}
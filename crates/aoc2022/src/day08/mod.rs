@@ -256,7 +256,7 @@ impl Day08 {
 
 impl Problem for Day08 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day08)
     }
     fn day(&self) -> u8 {
         8u8
@@ -812,7 +812,7 @@ impl Day09 {
 
 impl Problem for Day09 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2022, day09)
     }
     fn day(&self) -> u8 {
         9u8
@@ -82,6 +82,10 @@ static REPLACEMENTS: [(&str, &str); 9] = [
     ("nine", "n9e"),
 ];
 
+const PART1_EXAMPLE: &str = include_str!("examples/part1_a.txt");
+
+const PART2_EXAMPLE: &str = include_str!("examples/part2_a.txt");
+
 impl Day01 {
     fn solve_shared(&self, input: &str) -> Solution {
         let mut total = 0;
@@ -98,7 +102,7 @@ impl Day01 {
 
 impl Problem for Day01 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day01)
     }
     fn day(&self) -> u8 {
         1u8
@@ -106,6 +110,12 @@ impl Problem for Day01 {
     fn name(&self) -> &str {
         "Day 1: Trebuchet?!"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![PART1_EXAMPLE, PART2_EXAMPLE]
+    }
+    fn repeatable_input(&self) -> bool {
+        true
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         self.solve_shared(input)
     }
@@ -118,39 +128,9 @@ impl Problem for Day01 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U32(142));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part1(), Solution::U32(52974));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "two1nine
-        eightwothree
-        abcone2threexyz
-        xtwone3four
-        4nineeightseven2
-        zoneight234
-        7pqrstsixteen
-        ";
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U32(281));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day01 {};
-        assert_eq!(problem.solve_part2(), Solution::U32(53340));
-    }
+common::aoc_test! {
+    Day01 {};
+    part1: Solution::U32(52974), examples: [PART1_EXAMPLE => Solution::U32(142)];
+    part2: Solution::U32(53340), examples: [PART2_EXAMPLE => Solution::U32(281)];
+    variants: [crate::day01alt::Day01Alt {}];
 }
@@ -178,9 +178,13 @@ impl Day01Alt {
     }
 }
 
+const PART1_EXAMPLE: &str = include_str!("../day01/examples/part1_a.txt");
+
+const PART2_EXAMPLE: &str = include_str!("../day01/examples/part2_a.txt");
+
 impl Problem for Day01Alt {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day01)
     }
     fn day(&self) -> u8 {
         1u8
@@ -188,6 +192,15 @@ impl Problem for Day01Alt {
     fn name(&self) -> &str {
         "Day 1: Trebuchet?! (Alt)"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![PART1_EXAMPLE, PART2_EXAMPLE]
+    }
+    fn variant(&self) -> Option<&str> {
+        Some("Alt")
+    }
+    fn repeatable_input(&self) -> bool {
+        true
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let mut total = 0;
         for line in input.lines() {
@@ -204,39 +217,8 @@ impl Problem for Day01Alt {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        let problem = Day01Alt {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U32(142));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day01Alt {};
-        assert_eq!(problem.solve_part1(), Solution::U32(52974));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "two1nine
-        eightwothree
-        abcone2threexyz
-        xtwone3four
-        4nineeightseven2
-        zoneight234
-        7pqrstsixteen
-        ";
-        let problem = Day01Alt {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U32(281));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day01Alt {};
-        assert_eq!(problem.solve_part2(), Solution::U32(53340));
-    }
+common::aoc_test! {
+    Day01Alt {};
+    part1: Solution::U32(52974), examples: [PART1_EXAMPLE => Solution::U32(142)];
+    part2: Solution::U32(53340), examples: [PART2_EXAMPLE => Solution::U32(281)];
 }
@@ -95,9 +95,15 @@ use common::{Problem, Solution};
 /// For each game, find the minimum set of cubes that must have been present.
 /// *What is the sum of the power of these sets?*
 pub struct Day02;
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
 impl Problem for Day02 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day02)
     }
     fn day(&self) -> u8 {
         2u8
@@ -105,6 +111,9 @@ impl Problem for Day02 {
     fn name(&self) -> &str {
         "Day 2: Cube Conundrum"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let mut total = 0;
         for (idx, line) in input.lines().map(|l| l.trim()).enumerate() {
@@ -166,37 +175,8 @@ impl Problem for Day02 {
         Solution::U32(total)
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U32(8));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part1(), Solution::U32(2239));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U32(2286));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day02 {};
-        assert_eq!(problem.solve_part2(), Solution::U32(83435));
-    }
+common::aoc_test! {
+    Day02 {};
+    part1: Solution::U32(2239), examples: [EXAMPLE => Solution::U32(8)];
+    part2: Solution::U32(83435), examples: [EXAMPLE => Solution::U32(2286)];
 }
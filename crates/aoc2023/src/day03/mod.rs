@@ -175,9 +175,21 @@ impl GondolaEngine {
         self.parts_map[x][y]
     }
 }
+
+const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
 impl Problem for Day03 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day03)
     }
     fn day(&self) -> u8 {
         3u8
@@ -185,6 +197,9 @@ impl Problem for Day03 {
     fn name(&self) -> &str {
         "Day 3: Gear Ratios"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let mut engine = GondolaEngine::new();
         engine.parse(input);
@@ -213,47 +228,8 @@ impl Problem for Day03 {
         Solution::U32(total)
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..";
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U32(4361));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part1(), Solution::U32(527144));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..";
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U32(467835));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day03 {};
-        assert_eq!(problem.solve_part2(), Solution::U32(81463996));
-    }
+common::aoc_test! {
+    Day03 {};
+    part1: Solution::U32(527144), examples: [EXAMPLE => Solution::U32(4361)];
+    part2: Solution::U32(81463996), examples: [EXAMPLE => Solution::U32(467835)];
 }
@@ -144,9 +144,16 @@ fn card_iter(input: &str) -> impl Iterator<Item = (usize, u32)> + '_ {
         })
 }
 
+const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
 impl Problem for Day04 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day04)
     }
     fn day(&self) -> u8 {
         4u8
@@ -154,6 +161,9 @@ impl Problem for Day04 {
     fn name(&self) -> &str {
         "Day 4: Scratchcards"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let mut total = 0;
 
@@ -192,39 +202,8 @@ impl Problem for Day04 {
         Solution::U32(total)
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let problem = Day04 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U32(13));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day04 {};
-        assert_eq!(problem.solve_part1(), Solution::U32(25004));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let problem = Day04 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U32(30));
-    }
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day04 {};
-        assert_eq!(problem.solve_part2(), Solution::U32(14427616));
-    }
+common::aoc_test! {
+    Day04 {};
+    part1: Solution::U32(25004), examples: [EXAMPLE => Solution::U32(13)];
+    part2: Solution::U32(14427616), examples: [EXAMPLE => Solution::U32(30)];
 }
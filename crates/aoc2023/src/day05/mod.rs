@@ -1,4 +1,8 @@
-use common::{Problem, Solution};
+use common::{
+    generator::{InputGenerator, Rng},
+    interval::{Interval, IntervalSet, MapChain, PiecewiseMap},
+    Problem, Solution,
+};
 use std::str::FromStr;
 /// \--- Day 5: If You Give A Seed A Fertilizer ---
 /// ----------
@@ -183,40 +187,11 @@ use std::str::FromStr;
 /// any of the initial seed numbers?*
 pub struct Day05;
 
-#[derive(Debug)]
-struct Range {
-    dest: u64,
-    src: u64,
-    length: u64,
-}
-
-impl Range {
-    fn contains(&self, src: u64) -> bool {
-        src >= self.src && src < self.src + self.length
-    }
-
-    fn map(&self, src: u64) -> Option<u64> {
-        if self.contains(src) {
-            Some(self.dest + (src - self.src))
-        } else {
-            None
-        }
-    }
-
-    fn map_inverse(&self, dest: u64) -> Option<u64> {
-        if dest >= self.dest && dest < self.dest + self.length {
-            Some(self.src + (dest - self.dest))
-        } else {
-            None
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Almanac {
     seeds: Vec<u64>,
-    seed_ranges: Vec<(u64, u64)>,
-    mappings: Vec<Vec<Range>>,
+    seed_ranges: IntervalSet,
+    mappings: MapChain,
 }
 
 impl FromStr for Almanac {
@@ -234,17 +209,20 @@ impl FromStr for Almanac {
             .split(' ')
             .filter_map(|s| s.parse::<u64>().ok())
             .collect::<Vec<_>>();
-        let seed_ranges = seeds.chunks(2).map(|c| (c[0], c[0] + c[1] - 1)).collect::<Vec<_>>();
+        let seed_ranges = seeds
+            .chunks_exact(2)
+            .map(|c| Interval::with_length(c[0], c[1]))
+            .collect::<IntervalSet>();
 
         lines.next(); // blank
         lines.next(); // title
 
-        let mut mappings = Vec::new();
-        let mut current_group = Vec::new();
+        let mut mappings = MapChain::new();
+        let mut current_group = PiecewiseMap::new();
         for line in lines {
             if line.trim().is_empty() {
                 mappings.push(current_group);
-                current_group = Vec::new();
+                current_group = PiecewiseMap::new();
                 continue;
             }
 
@@ -252,7 +230,7 @@ impl FromStr for Almanac {
             if let (Some(dest), Some(src), Some(length)) =
                 (parts.next(), parts.next(), parts.next())
             {
-                current_group.push(Range { dest, src, length });
+                current_group.insert(Interval::with_length(src, length), dest);
             }
         }
         mappings.push(current_group);
@@ -262,37 +240,116 @@ impl FromStr for Almanac {
 }
 
 impl Almanac {
-    fn map_seed(&self, seed: u64) -> u64 {
-        self.mappings
-            .iter()
-            .fold(seed, |seed, group| group.iter().find_map(|r| r.map(seed)).unwrap_or(seed))
-    }
-
     fn lowest_location(&self) -> u64 {
-        self.seeds.iter().map(|&s| self.map_seed(s)).min().unwrap_or(0)
+        self.seeds.iter().map(|&s| self.mappings.map(s)).min().unwrap_or(0)
     }
 
     fn lowest_location_range(&self) -> u64 {
-        self.mappings
-            .iter()
-            .enumerate()
-            .flat_map(|(tier, ranges)| {
-                ranges.iter().filter_map(move |r| {
-                    Some(self.mappings.iter().take(tier + 1).rev().fold(r.dest, |seed, group| {
-                        group.iter().find_map(|g| g.map_inverse(seed)).unwrap_or(seed)
-                    }))
-                    .filter(|seed| self.seed_ranges.iter().any(|&(s, e)| s <= *seed && e >= *seed))
-                })
+        self.mappings.map_set(&self.seed_ranges).min().unwrap_or(u64::MAX)
+    }
+}
+
+impl Day05 {
+    /// Seeds and the `(dest, src, length)` triples of each map, with none of
+    /// the interval machinery.
+    fn parse_reference(input: &str) -> (Vec<u64>, Vec<Vec<[u64; 3]>>) {
+        let mut sections = input.split("\n\n");
+        let seeds =
+            sections.next().unwrap_or_default().split(' ').filter_map(|s| s.parse().ok()).collect();
+        let maps = sections
+            .map(|section| {
+                section
+                    .lines()
+                    .filter_map(|line| {
+                        let numbers =
+                            line.split(' ').filter_map(|s| s.parse().ok()).collect::<Vec<_>>();
+                        numbers.try_into().ok()
+                    })
+                    .collect()
             })
-            .map(|a| self.map_seed(a))
-            .min()
-            .unwrap_or(u64::MAX)
+            .collect();
+        (seeds, maps)
+    }
+
+    fn reference_location(maps: &[Vec<[u64; 3]>], seed: u64) -> u64 {
+        maps.iter().fold(seed, |value, map| {
+            map.iter()
+                .find(|[_, src, length]| (*src..src + length).contains(&value))
+                .map_or(value, |[dest, src, _]| dest + (value - src))
+        })
     }
 }
 
+const CATEGORIES: [&str; 8] =
+    ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+impl InputGenerator for Day05 {
+    /// `size` seed ranges, then maps that each cover about two thirds of
+    /// `0..100 * size` with up to `size` non-overlapping ranges.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let span = 100 * size as u64;
+        let seeds = (0..size)
+            .flat_map(|_| [rng.below(span), rng.between(1, span / 4)])
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+
+        for pair in CATEGORIES.windows(2) {
+            let mut cuts = (0..size).map(|_| rng.below(span)).collect::<Vec<_>>();
+            cuts.push(span);
+            cuts.sort_unstable();
+            cuts.dedup();
+            let mut section = vec![format!("{}-to-{} map:", pair[0], pair[1])];
+            for range in cuts.windows(2) {
+                if rng.ratio(2, 3) {
+                    let dest = rng.below(span);
+                    section.push(format!("{} {} {}", dest, range[0], range[1] - range[0]));
+                }
+            }
+            sections.push(section.join("\n"));
+        }
+
+        sections.join("\n\n")
+    }
+}
+
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
 impl Problem for Day05 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day05)
     }
     fn day(&self) -> u8 {
         5u8
@@ -300,6 +357,27 @@ impl Problem for Day05 {
     fn name(&self) -> &str {
         "Day 5: If You Give A Seed A Fertilizer"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+    fn reference_part1_with(&self, input: &str) -> Solution {
+        let (seeds, maps) = Day05::parse_reference(input);
+        Solution::U64(seeds.iter().map(|&s| Day05::reference_location(&maps, s)).min().unwrap_or(0))
+    }
+    fn reference_part2_with(&self, input: &str) -> Solution {
+        let (seeds, maps) = Day05::parse_reference(input);
+        Solution::U64(
+            seeds
+                .chunks_exact(2)
+                .flat_map(|c| c[0]..c[0] + c[1])
+                .map(|s| Day05::reference_location(&maps, s))
+                .min()
+                .unwrap_or(u64::MAX),
+        )
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         Solution::U64(input.parse::<Almanac>().map(|a| a.lowest_location()).unwrap_or(0))
     }
@@ -307,93 +385,30 @@ impl Problem for Day05 {
         Solution::U64(input.parse::<Almanac>().map(|a| a.lowest_location_range()).unwrap_or(0))
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "seeds: 79 14 55 13
 
-        seed-to-soil map:
-        50 98 2
-        52 50 48
-        
-        soil-to-fertilizer map:
-        0 15 37
-        37 52 2
-        39 0 15
-        
-        fertilizer-to-water map:
-        49 53 8
-        0 11 42
-        42 0 7
-        57 7 4
-        
-        water-to-light map:
-        88 18 7
-        18 25 70
-        
-        light-to-temperature map:
-        45 77 23
-        81 45 19
-        68 64 13
-        
-        temperature-to-humidity map:
-        0 69 1
-        1 0 69
-        
-        humidity-to-location map:
-        60 56 37
-        56 93 4";
-        let problem = Day05 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U64(35))
-    }
+common::aoc_test! {
+    Day05 {};
+    part1: Solution::U64(51752125), examples: [EXAMPLE => Solution::U64(35)];
+    part2: Solution::U64(12634632), examples: [EXAMPLE => Solution::U64(46)];
+
     #[test]
-    fn test_part1_real_input() {
+    fn test_generated_inputs() {
+        // Every range contains its start, so the lowest location over the
+        // ranges can't be above the lowest over just the starts.
         let problem = Day05 {};
-        assert_eq!(problem.solve_part1(), Solution::U64(51752125));
+        common::generator::check_property(&problem, 100, 20, |input| {
+            let (seeds, maps) = input.split_once('\n').unwrap();
+            let starts = seeds.split(' ').skip(1).step_by(2).collect::<Vec<_>>();
+            let starts_only = format!("seeds: {}\n{}", starts.join(" "), maps);
+            match (problem.solve_part2_with(input), problem.solve_part1_with(&starts_only)) {
+                (Solution::U64(ranges), Solution::U64(starts)) => ranges <= starts,
+                _ => false,
+            }
+        });
     }
-    #[test]
-    fn test_part2_example() {
-        let input = "seeds: 79 14 55 13
 
-        seed-to-soil map:
-        50 98 2
-        52 50 48
-        
-        soil-to-fertilizer map:
-        0 15 37
-        37 52 2
-        39 0 15
-        
-        fertilizer-to-water map:
-        49 53 8
-        0 11 42
-        42 0 7
-        57 7 4
-        
-        water-to-light map:
-        88 18 7
-        18 25 70
-        
-        light-to-temperature map:
-        45 77 23
-        81 45 19
-        68 64 13
-        
-        temperature-to-humidity map:
-        0 69 1
-        1 0 69
-        
-        humidity-to-location map:
-        60 56 37
-        56 93 4";
-        let problem = Day05 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U64(46))
-    }
     #[test]
-    fn test_part2_real_input() {
-        let problem = Day05 {};
-        assert_eq!(problem.solve_part2(), Solution::U64(12634632));
+    fn test_reference() {
+        assert_eq!(common::differential::check_reference(&Day05 {}), vec![]);
     }
 }
@@ -1,4 +1,7 @@
-use common::{Problem, Solution};
+use common::{
+    generator::{InputGenerator, Rng},
+    Problem, Solution,
+};
 /// \--- Day 6: Wait For It ---
 /// ----------
 ///
@@ -144,6 +147,16 @@ impl Race {
         (((-b + root) / denominator).ceil() as u64, ((-b - root) / denominator).floor() as u64)
     }
 
+    /// Furthest the boat can go, holding the button for half the race.
+    fn max_distance(time: u64) -> u64 {
+        (time / 2) * (time - time / 2)
+    }
+
+    /// Tries every possible hold time.
+    fn reference_winning_options(&self) -> u64 {
+        (0..=self.time).filter(|hold| hold * (self.time - hold) > self.distance).count() as u64
+    }
+
     fn winning_options(&self) -> u64 {
         let (a, b) = self.roots();
         a.max(b) - a.min(b) - 1
@@ -189,9 +202,41 @@ impl Day06 {
     }
 }
 
+/// Any more races and the joined race for part 2 wouldn't fit in a `u64`.
+const MAX_RACES: usize = 4;
+
+impl InputGenerator for Day06 {
+    /// Up to `MAX_RACES` races that can each be won, and still can be once
+    /// their digits are joined up for part 2.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        loop {
+            let races = (0..size.min(MAX_RACES))
+                .map(|_| {
+                    let time = rng.between(2, 30);
+                    Race::new(time, rng.below(Race::max_distance(time)))
+                })
+                .collect::<Vec<_>>();
+            let times = races.iter().map(|r| format!("{:>4}", r.time)).collect::<Vec<_>>();
+            let distances = races.iter().map(|r| format!("{:>4}", r.distance)).collect::<Vec<_>>();
+            let input = format!("Time:    {}\nDistance:{}", times.concat(), distances.concat());
+            let joined = self.parse_day2(&input);
+            if joined.distance < Race::max_distance(joined.time) {
+                return input;
+            }
+        }
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_RACES)
+    }
+}
+
+const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
 impl Problem for Day06 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day06)
     }
     fn day(&self) -> u8 {
         6u8
@@ -199,6 +244,18 @@ impl Problem for Day06 {
     fn name(&self) -> &str {
         "Day 6: Wait For It"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+    fn reference_part1_with(&self, input: &str) -> Solution {
+        Solution::U64(self.parse(input).iter().map(|r| r.reference_winning_options()).product())
+    }
+    fn reference_part2_with(&self, input: &str) -> Solution {
+        Solution::U64(self.parse_day2(input).reference_winning_options())
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         Solution::U64(self.parse(input).into_iter().map(|r| r.winning_options()).product::<u64>())
     }
@@ -207,31 +264,13 @@ impl Problem for Day06 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "Time:      7  15   30
-        Distance:  9  40  200";
-        let problem = Day06 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U64(288));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day06 {};
-        assert_eq!(problem.solve_part1(), Solution::U64(449820));
-    }
-    #[test]
-    fn test_part2_example() {
-        let input = "Time:      7  15   30
-        Distance:  9  40  200";
-        let problem = Day06 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U64(71503));
-    }
+common::aoc_test! {
+    Day06 {};
+    part1: Solution::U64(449820), examples: [EXAMPLE => Solution::U64(288)];
+    part2: Solution::U64(42250895), examples: [EXAMPLE => Solution::U64(71503)];
+
     #[test]
-    fn test_part2_real_input() {
-        let problem = Day06 {};
-        assert_eq!(problem.solve_part2(), Solution::U64(42250895));
+    fn test_reference() {
+        assert_eq!(common::differential::check_reference(&Day06 {}), vec![]);
     }
 }
@@ -382,9 +382,15 @@ impl Day07 {
     }
 }
 
+const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
 impl Problem for Day07 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day07)
     }
     fn day(&self) -> u8 {
         7u8
@@ -392,6 +398,9 @@ impl Problem for Day07 {
     fn name(&self) -> &str {
         "Day 7: Camel Cards"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![EXAMPLE]
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         Solution::U64(self.solve_shared(input, false))
     }
@@ -400,41 +409,8 @@ impl Problem for Day07 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1_example() {
-        let input = "32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483";
-        let problem = Day07 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U64(6440));
-    }
-
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day07 {};
-        assert_eq!(problem.solve_part1(), Solution::U64(250957639));
-    }
-
-    #[test]
-    fn test_part2_example() {
-        let input = "32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483";
-        let problem = Day07 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U64(5905));
-    }
-
-    #[test]
-    fn test_part2_real_input() {
-        let problem = Day07 {};
-        assert_eq!(problem.solve_part2(), Solution::U64(251515496));
-    }
+common::aoc_test! {
+    Day07 {};
+    part1: Solution::U64(250957639), examples: [EXAMPLE => Solution::U64(6440)];
+    part2: Solution::U64(251515496), examples: [EXAMPLE => Solution::U64(5905)];
 }
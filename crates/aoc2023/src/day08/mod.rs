@@ -1,4 +1,8 @@
-use common::{Problem, Solution};
+use common::{
+    cycle::{record_cycle, CycleInfo},
+    math::Cycle,
+    Problem, Solution,
+};
 use rustc_hash::FxHashMap;
 /// \--- Day 8: Haunted Wasteland ---
 /// ----------
@@ -136,19 +140,6 @@ struct Network {
     nodes: FxHashMap<u16, Node>,
 }
 
-const fn gcd(a: u64, b: u64) -> u64 {
-    let mut a = a;
-    let mut b = b;
-    while b != 0 {
-        (b, a) = (a % b, b);
-    }
-    a
-}
-
-const fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
-}
-
 const fn as_num(c: char) -> u16 {
     match c {
         'A' => 0,
@@ -226,53 +217,45 @@ impl Network {
         total_steps
     }
 
-    fn ghost_traverse(&self, moves: &[Move]) -> u64 {
-        let mut total_steps = 0;
-        let mut current: [Option<u16>; 16] = [None; 16];
-        let mut required_matches = 0;
-        for (i, k) in self.nodes.keys().filter(|n| *n & 0b11111 == 0).enumerate() {
-            current[i] = Some(*k);
-            required_matches += 1;
-        }
-        let mut last_match: [Option<u16>; 16] = [None; 16];
-        let mut last_cycle_count = [0; 16];
-        let mut i: usize = 0;
-
-        loop {
-            total_steps += 1;
-            let current_move = moves[i];
-
-            let mut next = [None; 16];
-            for (idx, current_node) in
-                current.iter().enumerate().filter_map(|(idx, &n)| n.map(|n| (idx, n)))
-            {
-                let node = self.nodes.get(&current_node).expect("Invalid node");
-                let next_node = match current_move {
-                    Move::Left => &node.left,
-                    Move::Right => &node.right,
-                };
-                next[idx] = Some(*next_node);
-                if (next_node & as_num('Z') == as_num('Z')) && last_match[idx].is_none() {
-                    last_match[idx] = Some(*next_node);
-                    last_cycle_count[idx] = total_steps;
-                }
-            }
+    /// Follows a single ghost from `start` until its (node, instruction) state
+    /// repeats, returning every step on which it can be standing on a `Z`
+    /// node. Hits inside the repeating section recur with the loop's period.
+    fn ghost_cycles(&self, start: u16, moves: &[Move]) -> Vec<Cycle> {
+        let history = record_cycle((start, 0), |&(current, i)| {
+            let node = self.nodes.get(&current).expect("Invalid node");
+            let next = match moves[i] {
+                Move::Left => node.left,
+                Move::Right => node.right,
+            };
+            (next, (i + 1) % moves.len())
+        });
 
-            if last_match.iter().take(required_matches).all(|&b| b.is_some()) {
-                let all_lcm =
-                    last_cycle_count.iter().take(required_matches).fold(1, |acc, &n| lcm(acc, n));
-                total_steps = all_lcm;
-                break;
-            }
+        let CycleInfo { prefix, period } = history.info;
+        history
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, (node, _))| node & 0b11111 == as_num('Z'))
+            .map(|(step, _)| {
+                Cycle::new(step as u64, if step >= prefix { period as u64 } else { 0 })
+            })
+            .collect()
+    }
 
-            i += 1;
-            if i >= moves.len() {
-                i = 0;
-            }
-            current = next;
+    fn ghost_traverse(&self, moves: &[Move]) -> u64 {
+        // Every ghost contributes a set of cycles on which it is at a `Z` node,
+        // and all of them need to line up at once. Keep every combination that
+        // still can, since a ghost may pass through more than one `Z` node.
+        let mut candidates = vec![Cycle::new(0, 1)];
+        for start in self.nodes.keys().filter(|n| *n & 0b11111 == as_num('A')) {
+            let cycles = self.ghost_cycles(*start, moves);
+            candidates = candidates
+                .iter()
+                .flat_map(|candidate| cycles.iter().filter_map(|c| candidate.combine(c)))
+                .collect();
         }
 
-        total_steps
+        candidates.iter().map(|c| c.offset).min().unwrap_or(0)
     }
 }
 
@@ -303,9 +286,15 @@ impl Day08 {
     }
 }
 
+const PART1_EXAMPLE: &str = include_str!("examples/part1_a.txt");
+
+const PART1_EXAMPLE_2: &str = include_str!("examples/part1_b.txt");
+
+const PART2_EXAMPLE: &str = include_str!("examples/part2_a.txt");
+
 impl Problem for Day08 {
     fn problem_input(&self) -> &'static str {
-        include_str!("input.txt")
+        common::problem_input!(2023, day08)
     }
     fn day(&self) -> u8 {
         8u8
@@ -313,6 +302,9 @@ impl Problem for Day08 {
     fn name(&self) -> &str {
         "Day 8: Haunted Wasteland"
     }
+    fn examples(&self) -> Vec<&'static str> {
+        vec![PART1_EXAMPLE, PART1_EXAMPLE_2, PART2_EXAMPLE]
+    }
     fn solve_part1_with(&self, input: &str) -> Solution {
         let (moves, network) = self.parse(input);
         Solution::U16(network.traverse(&moves))
@@ -323,52 +315,27 @@ impl Problem for Day08 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_part1_example() {
-        let input = "RL
-
-            AAA = (BBB, CCC)
-            BBB = (DDD, EEE)
-            CCC = (ZZZ, GGG)
-            DDD = (DDD, DDD)
-            EEE = (EEE, EEE)
-            GGG = (GGG, GGG)
-            ZZZ = (ZZZ, ZZZ)";
-        let problem = Day08 {};
-        assert_eq!(problem.solve_part1_with(input), Solution::U16(2));
-        let input2 = "LLR
+common::aoc_test! {
+    Day08 {};
+    part1: Solution::U16(18827), examples: [
+        PART1_EXAMPLE => Solution::U16(2),
+        PART1_EXAMPLE_2 => Solution::U16(6),
+    ];
+    part2: Solution::U64(20220305520997), examples: [PART2_EXAMPLE => Solution::U64(6)];
 
-            AAA = (BBB, BBB)
-            BBB = (AAA, ZZZ)
-            ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(problem.solve_part1_with(input2), Solution::U16(6));
-    }
-    #[test]
-    fn test_part1_real_input() {
-        let problem = Day08 {};
-        assert_eq!(problem.solve_part1(), Solution::U16(18827));
-    }
     #[test]
-    fn test_part2_example() {
-        let input = "LR
+    fn test_part2_offset_cycles() {
+        let input = "L
 
-            11A = (11B, XXX)
-            11B = (XXX, 11Z)
-            11Z = (11B, XXX)
-            22A = (22B, XXX)
-            22B = (22C, 22C)
-            22C = (22Z, 22Z)
+            11A = (11B, 11B)
+            11B = (11Z, 11Z)
+            11Z = (11C, 11C)
+            11C = (11Z, 11Z)
+            22A = (22Z, 22Z)
             22Z = (22B, 22B)
-            XXX = (XXX, XXX)";
-        let problem = Day08 {};
-        assert_eq!(problem.solve_part2_with(input), Solution::U64(6));
-    }
-    #[test]
-    fn test_part2_real_input() {
+            22B = (22C, 22C)
+            22C = (22Z, 22Z)";
         let problem = Day08 {};
-        assert_eq!(problem.solve_part2(), Solution::U64(20220305520997));
+        assert_eq!(problem.solve_part2_with(input), Solution::U64(4));
     }
 }
@@ -23,6 +23,9 @@ use day07::*;
 use day08::*;
 fn main() {
     let args = std::env::args().collect::<Vec<_>>();
+    if let Some(dir) = args.iter().position(|a| a == "--inputs").and_then(|idx| args.get(idx + 1)) {
+        common::input::set_inputs_dir(dir);
+    }
     let problems: Vec<Box<dyn Problem>> = vec![
         Box::new(Day01),
         Box::new(Day01Alt),
@@ -34,7 +37,9 @@ fn main() {
         Box::new(Day07),
         Box::new(Day08),
     ];
-    if args.contains(&"bench".to_string()) {
+    if args.contains(&"bench".to_string()) && args.contains(&"--scaling".to_string()) {
+        common::scaling::scale_all(&problems);
+    } else if args.contains(&"bench".to_string()) {
         for problem in problems {
             let bench = problem.bench_part1();
             println!(
@@ -52,7 +57,7 @@ fn main() {
             );
         }
     } else if args.contains(&"bench-md".to_string()) {
-        let mut collection = BenchmarkCollection::new(format!("Advent of Code {}", 2023u32));
+        let mut collection = BenchmarkCollection::new(format!("Advent of Code {}", 2023));
         for problem in problems {
             let bench = problem.bench_part1();
             collection.add(bench);
@@ -60,6 +65,51 @@ fn main() {
             collection.add(bench);
         }
         println!("{}", collection.to_markdown());
+    } else if args.contains(&"progress".to_string()) {
+        let progress = common::progress::YearProgress::collect(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        print!("{}", progress.calendar());
+    } else if args.contains(&"progress-md".to_string()) {
+        let progress = common::progress::YearProgress::collect(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        let readme = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+        common::progress::write_readme(std::path::Path::new(readme), &progress)
+            .expect("Failed to write README");
+    } else if args.contains(&"all-inputs".to_string()) {
+        let failures = common::input_sets::check_all(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            2023,
+        );
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"diff".to_string()) {
+        let failures = common::differential::check_all(&problems);
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"reference".to_string()) {
+        let failures = common::differential::check_references(&problems);
+        if failures > 0 {
+            std::process::exit(1);
+        }
+    } else if args.contains(&"examples".to_string()) {
+        let day = args.iter().skip_while(|a| *a != "examples").nth(1).and_then(|d| d.parse().ok());
+        let failures = common::examples::check_all(
+            &problems,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+            day,
+        );
+        if failures > 0 {
+            std::process::exit(1);
+        }
     } else {
         for problem in problems {
             println!("{} - Part 1: {}", problem.name(), problem.solve_part1());
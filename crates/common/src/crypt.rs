@@ -0,0 +1,125 @@
+use std::{
+    fmt::{Display, Formatter, Write},
+    path::PathBuf,
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use config_better::Config;
+
+/// Environment variable holding the input key as hex, which takes priority
+/// over the key file.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+const NONCE_SIZE: usize = 12;
+
+#[derive(Debug)]
+pub enum KeyError {
+    Missing(PathBuf),
+    Invalid(String),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::Missing(path) => write!(
+                f,
+                "No input key found in {} or at {:?}. Ask a teammate for the key, or create one \
+                 with `aoc-update gen-key`",
+                KEY_VAR, path
+            ),
+            KeyError::Invalid(source) => {
+                write!(f, "The input key from {} is not 64 hex characters", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+/// Key used to encrypt puzzle inputs at rest with ChaCha20-Poly1305.
+#[derive(Clone)]
+pub struct InputKey(Key);
+
+impl InputKey {
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn default_path() -> PathBuf {
+        Config::new("aoc-update").config.path.join("input.key")
+    }
+
+    /// Reads the key from `AOC_INPUT_KEY`, or else from the key file in the
+    /// `aoc-update` config directory.
+    pub fn load() -> Result<Self, KeyError> {
+        if let Ok(hex) = std::env::var(KEY_VAR) {
+            return Self::from_hex(&hex).ok_or_else(|| KeyError::Invalid(KEY_VAR.to_owned()));
+        }
+
+        let path = Self::default_path();
+        let hex = std::fs::read_to_string(&path).map_err(|_| KeyError::Missing(path.clone()))?;
+        Self::from_hex(&hex).ok_or_else(|| KeyError::Invalid(format!("{:?}", path)))
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+        let mut key = Key::default();
+        for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+        }
+        Some(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{:02x}", b);
+            hex
+        })
+    }
+
+    /// Encrypts `plaintext`, returning the random nonce followed by the
+    /// ciphertext.
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, plaintext.as_bytes())
+            .expect("Encrypting into a Vec cannot fail");
+        nonce.into_iter().chain(ciphertext).collect()
+    }
+
+    /// Reverses [`InputKey::encrypt`], returning `None` if the data was
+    /// encrypted with a different key or has been tampered with.
+    pub fn decrypt(&self, data: &[u8]) -> Option<String> {
+        if data.len() < NONCE_SIZE {
+            return None;
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        let plaintext =
+            ChaCha20Poly1305::new(&self.0).decrypt(Nonce::from_slice(nonce), ciphertext).ok()?;
+        String::from_utf8(plaintext).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = InputKey::generate();
+        let encrypted = key.encrypt("1abc2\npqr3stu8vwx\n");
+        assert_ne!(&encrypted[NONCE_SIZE..], b"1abc2\npqr3stu8vwx\n");
+        assert_eq!(key.decrypt(&encrypted).as_deref(), Some("1abc2\npqr3stu8vwx\n"));
+
+        let restored = InputKey::from_hex(&key.to_hex()).unwrap();
+        assert_eq!(restored.decrypt(&encrypted).as_deref(), Some("1abc2\npqr3stu8vwx\n"));
+        assert_eq!(InputKey::generate().decrypt(&encrypted), None);
+        assert!(InputKey::from_hex("abc").is_none());
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Shape of the sequence `start, f(start), f(f(start)), ...` once it starts
+/// repeating: the first `prefix` states are never seen again, and every state
+/// after that repeats every `period` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleInfo {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl CycleInfo {
+    /// The earliest step that is guaranteed to hold the same state as `step`.
+    pub fn reduce(&self, step: u64) -> u64 {
+        let prefix = self.prefix as u64;
+        if step < prefix {
+            step
+        } else {
+            prefix + (step - prefix) % self.period as u64
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Only ever keeps three states in memory, at the
+/// cost of evaluating `f` roughly three times per step.
+pub fn floyd<T, F>(start: T, mut f: F) -> CycleInfo
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    CycleInfo { prefix, period }
+}
+
+/// Brent's algorithm. Uses as little memory as [`floyd`] but usually needs
+/// fewer evaluations of `f`.
+pub fn brent<T, F>(start: T, mut f: F) -> CycleInfo
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    CycleInfo { prefix, period }
+}
+
+/// Every state visited before the sequence repeats, along with the shape of
+/// the cycle.
+#[derive(Clone, Debug)]
+pub struct CycleHistory<T> {
+    pub info: CycleInfo,
+    pub states: Vec<T>,
+}
+
+impl<T> CycleHistory<T> {
+    /// The state after `step` steps, however large.
+    pub fn state_at(&self, step: u64) -> &T {
+        &self.states[self.info.reduce(step) as usize]
+    }
+}
+
+/// Hash-based detection, which evaluates `f` exactly once per distinct state
+/// and remembers all of them.
+pub fn record_cycle<T, F>(start: T, mut f: F) -> CycleHistory<T>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut current = start;
+    loop {
+        if let Some(&prefix) = seen.get(&current) {
+            let period = states.len() - prefix;
+            return CycleHistory { info: CycleInfo { prefix, period }, states };
+        }
+        let next = f(&current);
+        seen.insert(current.clone(), states.len());
+        states.push(current);
+        current = next;
+    }
+}
+
+/// Hash-based detection, keeping only the shape of the cycle.
+pub fn find_cycle<T, F>(start: T, f: F) -> CycleInfo
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    record_cycle(start, f).info
+}
+
+/// The state after applying `f` a total of `steps` times, skipping over
+/// whole cycles once one is found.
+pub fn state_after<T, F>(start: T, steps: u64, mut f: F) -> T
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut current = start;
+    for step in 0..steps {
+        if let Some(&prefix) = seen.get(&current) {
+            let info = CycleInfo { prefix, period: step as usize - prefix };
+            return states.swap_remove(info.reduce(steps) as usize);
+        }
+        let next = f(&current);
+        seen.insert(current.clone(), states.len());
+        states.push(current);
+        current = next;
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 2 -> ...
+    fn rho(n: &u32) -> u32 {
+        if *n == 6 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let expected = CycleInfo { prefix: 2, period: 5 };
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(find_cycle(0, rho), expected);
+        assert_eq!(find_cycle(2, rho), CycleInfo { prefix: 0, period: 5 });
+    }
+
+    #[test]
+    fn test_state_after() {
+        assert_eq!(state_after(0, 0, rho), 0);
+        assert_eq!(state_after(0, 4, rho), 4);
+        assert_eq!(state_after(0, 7, rho), 2);
+        assert_eq!(state_after(0, 1_000_000_000_000, rho), 5);
+        assert_eq!(*record_cycle(0, rho).state_at(1_000_000_000_000), 5);
+    }
+}
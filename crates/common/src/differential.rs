@@ -0,0 +1,377 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{
+    generator::Rng,
+    input_sets::{panic_message, quietly, solve, Outcome},
+    Problem, Solution,
+};
+
+/// Variants that gave different answers for the same part of the same input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    /// Where the input came from, e.g. `real input` or `example 1`.
+    pub source: String,
+    /// The smallest input found that still makes the variants disagree.
+    pub input: String,
+    /// Each variant's name and answer for the minimized input.
+    pub answers: Vec<(String, String)>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Day {} part {} disagrees on {}, minimized to:",
+            self.day, self.part, self.source
+        )?;
+        for line in self.input.lines() {
+            writeln!(f, "    {}", line)?;
+        }
+        for (name, answer) in self.answers.iter() {
+            writeln!(f, "  {}: {}", name, answer)?;
+        }
+        Ok(())
+    }
+}
+
+fn answer(problem: &dyn Problem, part: u8, input: &str) -> Option<String> {
+    let outcome = match part {
+        1 => solve(|| problem.solve_part1_with(input), None),
+        _ => solve(|| problem.solve_part2_with(input), None),
+    };
+    match outcome? {
+        Outcome::Panicked(message) => Some(format!("panicked: {}", message)),
+        Outcome::Unchecked(Solution::Error) => Some(Solution::Error.to_string()),
+        Outcome::Correct(s) | Outcome::Unchecked(s) | Outcome::Wrong { actual: s, .. } => {
+            Some(s.to_string())
+        }
+    }
+}
+
+/// Every variant's answer, leaving out those that haven't solved this part.
+fn answers(variants: &[&dyn Problem], part: u8, input: &str) -> Vec<(String, String)> {
+    variants.iter().filter_map(|p| Some((p.name().to_owned(), answer(*p, part, input)?))).collect()
+}
+
+fn disagree(answers: &[(String, String)]) -> bool {
+    answers.windows(2).any(|pair| pair[0].1 != pair[1].1)
+}
+
+/// Removes as many items as possible while `fails` still holds, trying large
+/// chunks first (a simplified delta debugging).
+fn reduce<T: Clone>(mut items: Vec<T>, fails: impl Fn(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = (items.len() / 2).max(1);
+    while items.len() > 1 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < items.len() && items.len() > 1 {
+            let end = (start + chunk).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+            if !candidate.is_empty() && fails(&candidate) {
+                items = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+    }
+    items
+}
+
+/// Shrinks `input` line by line, then character by character within each
+/// remaining line, for as long as `fails` holds.
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let lines = input.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+    let mut lines = reduce(lines, |lines| fails(&lines.join("\n")));
+
+    for idx in 0..lines.len() {
+        let chars = lines[idx].chars().collect::<Vec<_>>();
+        let reduced = reduce(chars, |chars| {
+            let mut candidate = lines.clone();
+            candidate[idx] = chars.iter().collect();
+            fails(&candidate.join("\n"))
+        });
+        lines[idx] = reduced.into_iter().collect();
+    }
+
+    lines.join("\n")
+}
+
+fn find_disagreements(
+    variants: &[&dyn Problem],
+    inputs: &[(String, String)],
+    shrink: bool,
+) -> Vec<Disagreement> {
+    let Some(day) = variants.first().map(|p| p.day()) else {
+        return vec![];
+    };
+
+    quietly(|| {
+        let mut disagreements = vec![];
+        for part in 1..=2 {
+            for (source, input) in inputs {
+                if !disagree(&answers(variants, part, input)) {
+                    continue;
+                }
+                let input = if shrink {
+                    minimize(input, |input| disagree(&answers(variants, part, input)))
+                } else {
+                    input.clone()
+                };
+                disagreements.push(Disagreement {
+                    day,
+                    part,
+                    source: source.clone(),
+                    answers: answers(variants, part, &input),
+                    input,
+                });
+            }
+        }
+        disagreements
+    })
+}
+
+/// Runs every variant on every input and reports where they disagree, with the
+/// input minimized.
+pub fn check_variants(variants: &[&dyn Problem], inputs: &[(String, String)]) -> Vec<Disagreement> {
+    find_disagreements(variants, inputs, true)
+}
+
+/// Sizes of the inputs generated for days that have a generator, smallest first
+/// so disagreements are cheap to minimize.
+const GENERATED_SIZES: [usize; 8] = [1, 2, 3, 5, 8, 13, 21, 34];
+
+/// Groups problems by day, then checks that the variants of each day agree on
+/// the real input, the examples and any generated inputs. Returns the number
+/// of disagreements.
+pub fn check_all(problems: &[Box<dyn Problem>]) -> usize {
+    let mut days: BTreeMap<u8, Vec<&dyn Problem>> = BTreeMap::new();
+    for problem in problems {
+        days.entry(problem.day()).or_default().push(problem.as_ref());
+    }
+
+    let mut failures = 0;
+    for variants in days.values().filter(|v| v.len() > 1) {
+        let primary = variants.iter().find(|p| p.variant().is_none()).unwrap_or(&variants[0]);
+        // A missing or undecryptable input panics, which shouldn't stop the
+        // other days from being checked.
+        let mut inputs = vec![];
+        match quietly(|| catch_unwind(AssertUnwindSafe(|| primary.problem_input()))) {
+            Ok(input) => inputs.push(("real input".to_owned(), input.to_owned())),
+            Err(payload) => {
+                println!(
+                    "{} - couldn't load the real input: {}",
+                    primary.name(),
+                    panic_message(payload.as_ref())
+                );
+                failures += 1;
+            }
+        }
+        for (idx, example) in primary.examples().into_iter().enumerate() {
+            inputs.push((format!("example {}", idx + 1), example.to_owned()));
+        }
+
+        if let Some(generator) = primary.generator() {
+            for (seed, size) in GENERATED_SIZES.iter().enumerate() {
+                let input = generator.generate(&mut Rng::new(seed as u64), *size);
+                inputs.push((format!("generated input (size {}, seed {})", size, seed), input));
+            }
+        }
+
+        let disagreements = check_variants(variants, &inputs);
+        if disagreements.is_empty() {
+            println!(
+                "{} - {} variants agree on {} inputs",
+                primary.name(),
+                variants.len(),
+                inputs.len()
+            );
+        }
+        for disagreement in disagreements.iter() {
+            print!("{}", disagreement);
+        }
+        failures += disagreements.len();
+    }
+    failures
+}
+
+/// A day's reference solution, presented as a variant of it.
+struct Reference<'a>(&'a dyn Problem);
+
+impl Problem for Reference<'_> {
+    fn problem_input(&self) -> &'static str {
+        self.0.problem_input()
+    }
+
+    fn day(&self) -> u8 {
+        self.0.day()
+    }
+
+    fn name(&self) -> &str {
+        "reference"
+    }
+
+    fn solve_part1_with(&self, input: &str) -> Solution {
+        self.0.reference_part1_with(input)
+    }
+
+    fn solve_part2_with(&self, input: &str) -> Solution {
+        self.0.reference_part2_with(input)
+    }
+}
+
+/// Number of generated inputs of each size to check a reference solution
+/// against, and the largest size to use. Reference solutions are slow, so these
+/// are kept small.
+const REFERENCE_CASES: u64 = 20;
+const REFERENCE_MAX_SIZE: usize = 3;
+
+/// Compares a problem with its reference solution on small generated inputs,
+/// reporting the smallest disagreement for each part. Days without a
+/// generator, or parts without a reference, are skipped.
+///
+/// The inputs aren't minimized, since cutting lines out of a generated input
+/// rarely leaves a valid one, and the generated inputs are already small.
+pub fn check_reference(problem: &dyn Problem) -> Vec<Disagreement> {
+    let Some(generator) = problem.generator() else {
+        return vec![];
+    };
+    let inputs = (1..=REFERENCE_MAX_SIZE)
+        .flat_map(|size| {
+            (0..REFERENCE_CASES).map(move |seed| {
+                let input = generator.generate(&mut Rng::new(seed), size);
+                (format!("generated input (size {}, seed {})", size, seed), input)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut disagreements = find_disagreements(&[problem, &Reference(problem)], &inputs, false);
+    disagreements.dedup_by_key(|d| d.part);
+    disagreements
+}
+
+/// Checks every problem that has a reference solution against it. Returns the
+/// number of disagreements.
+pub fn check_references(problems: &[Box<dyn Problem>]) -> usize {
+    let mut failures = 0;
+    for problem in problems {
+        let disagreements = check_reference(problem.as_ref());
+        for disagreement in disagreements.iter() {
+            print!("{}", disagreement);
+        }
+        failures += disagreements.len();
+    }
+    println!("{} disagreements with reference solutions", failures);
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts lines, but the careless variant also counts lines with an `x`
+    /// twice.
+    struct Lines(bool);
+
+    impl Problem for Lines {
+        fn problem_input(&self) -> &'static str {
+            "a\nb\nc"
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            if self.0 {
+                "Careless"
+            } else {
+                "Careful"
+            }
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            let doubled =
+                if self.0 { input.lines().filter(|l| l.contains('x')).count() } else { 0 };
+            (input.lines().count() + doubled).into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+    }
+
+    #[test]
+    fn test_minimize() {
+        let input = "abc\ndef\nghxij\nklm\nxyz";
+        assert_eq!(minimize(input, |s| s.contains('x')), "x");
+        assert_eq!(minimize(input, |s| s.lines().count() >= 2).lines().count(), 2);
+    }
+
+    #[test]
+    fn test_check_variants() {
+        let variants: [&dyn Problem; 2] = [&Lines(false), &Lines(true)];
+        let inputs = vec![
+            ("agreeing".to_owned(), "abc\ndef".to_owned()),
+            ("disagreeing".to_owned(), "abc\ndef\nghxij\nklm".to_owned()),
+        ];
+        let disagreements = check_variants(&variants, &inputs);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].source, "disagreeing");
+        assert_eq!(disagreements[0].part, 1);
+        assert_eq!(disagreements[0].input, "x");
+        assert_eq!(
+            disagreements[0].answers,
+            [("Careful".to_owned(), "1".to_owned()), ("Careless".to_owned(), "2".to_owned())]
+        );
+    }
+
+    /// A day whose input can't be loaded.
+    struct Unreadable(&'static str);
+
+    impl Problem for Unreadable {
+        fn problem_input(&self) -> &'static str {
+            panic!("no input for {}", self.0)
+        }
+
+        fn day(&self) -> u8 {
+            2
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            input.lines().count().into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+    }
+
+    #[test]
+    fn test_check_all_missing_input() {
+        let problems: Vec<Box<dyn Problem>> = vec![
+            Box::new(Unreadable("First")),
+            Box::new(Unreadable("Second")),
+            Box::new(Lines(false)),
+            Box::new(Lines(true)),
+        ];
+        // The unreadable day is a failure, and day 1 is still checked.
+        assert_eq!(check_all(&problems), 1);
+    }
+}
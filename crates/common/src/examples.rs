@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    input_sets::{quietly, solve},
+    Problem,
+};
+
+/// Directory inside a day's module with its examples, e.g. `day01/examples`.
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// File in the examples directory with the expected answers, one example per
+/// line as `part1_a: 142`. An example without an answer is left blank.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// An example input from the puzzle description, stored as
+/// `<examples>/part<part>_<letter>.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The file name without its extension, e.g. `part1_a`.
+    pub name: String,
+    pub part: u8,
+    pub input: String,
+    pub expected: Option<String>,
+}
+
+/// Where the examples for `day` are kept, given a year crate's `src`
+/// directory. Variants share the examples of the day they're a variant of.
+pub fn examples_dir(src_dir: &Path, day: u8) -> PathBuf {
+    src_dir.join(format!("day{:02}", day)).join(EXAMPLES_DIR)
+}
+
+/// Name of the `idx`th example of a part: `part1_a`, `part1_b` and so on.
+pub fn example_name(part: u8, idx: usize) -> String {
+    let suffix = match u8::try_from(idx) {
+        Ok(idx) if idx < 26 => char::from(b'a' + idx).to_string(),
+        _ => idx.to_string(),
+    };
+    format!("part{}_{}", part, suffix)
+}
+
+/// The part an example file is for, if it's named like one.
+fn example_part(name: &str) -> Option<u8> {
+    let (part, suffix) = name.strip_prefix("part")?.split_once('_')?;
+    match part.parse::<u8>() {
+        Ok(part @ (1 | 2)) if !suffix.is_empty() => Some(part),
+        _ => None,
+    }
+}
+
+pub fn parse_answers(answers: &str) -> Vec<(String, Option<String>)> {
+    answers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, answer)| {
+            let answer = Some(answer.trim().to_owned()).filter(|a| !a.is_empty());
+            (name.trim().to_owned(), answer)
+        })
+        .collect()
+}
+
+pub fn format_answers(answers: &[(String, Option<String>)]) -> String {
+    answers
+        .iter()
+        .map(|(name, answer)| match answer {
+            Some(answer) => format!("{}: {}\n", name, answer),
+            None => format!("{}:\n", name),
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Reads every example in `dir`, sorted by name, with its expected answer.
+/// A missing directory has no examples.
+pub fn load(dir: &Path) -> std::io::Result<Vec<Example>> {
+    let answers = match std::fs::read_to_string(dir.join(ANSWERS_FILE)) {
+        Ok(answers) => parse_answers(&answers),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e),
+    };
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut examples = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(|s| s.to_owned()) else {
+            continue;
+        };
+        let Some(part) = example_part(&name) else {
+            continue;
+        };
+        let expected = answers.iter().find(|(n, _)| *n == name).and_then(|(_, a)| a.clone());
+        examples.push(Example { input: std::fs::read_to_string(&path)?, name, part, expected });
+    }
+    examples.sort_by(|a, b| (a.part, &a.name).cmp(&(b.part, &b.name)));
+    Ok(examples)
+}
+
+/// Runs every problem, or just those for `day`, against its day's examples,
+/// printing each result and a summary of the failures. Returns the number of
+/// failures.
+pub fn check_all(problems: &[Box<dyn Problem>], src_dir: &str, day: Option<u8>) -> usize {
+    let mut failures = vec![];
+    let mut count = 0;
+
+    quietly(|| {
+        for problem in problems.iter().filter(|p| day.is_none_or(|day| p.day() == day)) {
+            let examples = match load(&examples_dir(Path::new(src_dir), problem.day())) {
+                Ok(examples) => examples,
+                Err(e) => {
+                    failures.push(format!("{} - couldn't load examples: {}", problem.name(), e));
+                    continue;
+                }
+            };
+            for example in examples {
+                let outcome = match example.part {
+                    1 => solve(
+                        || problem.solve_part1_with(&example.input),
+                        example.expected.as_ref(),
+                    ),
+                    _ => solve(
+                        || problem.solve_part2_with(&example.input),
+                        example.expected.as_ref(),
+                    ),
+                };
+                let Some(outcome) = outcome else {
+                    continue;
+                };
+                count += 1;
+                let line = format!("{} - {}: {}", problem.name(), example.name, outcome);
+                println!("{}", line);
+                if outcome.is_failure() {
+                    failures.push(line);
+                }
+            }
+        }
+    });
+
+    if failures.is_empty() {
+        println!("\nAll {} examples passed", count);
+    } else {
+        println!("\n{} failures:", failures.len());
+        for failure in failures.iter() {
+            println!("  {}", failure);
+        }
+    }
+    failures.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct Lines;
+
+    impl Problem for Lines {
+        fn problem_input(&self) -> &'static str {
+            ""
+        }
+
+        fn day(&self) -> u8 {
+            3
+        }
+
+        fn name(&self) -> &str {
+            "Lines"
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            input.lines().count().into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(example_name(1, 0), "part1_a");
+        assert_eq!(example_name(2, 25), "part2_z");
+        assert_eq!(example_name(2, 26), "part2_26");
+        assert_eq!(example_part("part2_b"), Some(2));
+        assert_eq!(example_part("part3_a"), None);
+        assert_eq!(example_part("part1_"), None);
+        assert_eq!(example_part("notes"), None);
+    }
+
+    #[test]
+    fn test_load_and_check() {
+        let src = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let dir = examples_dir(&src, 3);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("part1_a.txt"), "a\nb\n").unwrap();
+        std::fs::write(dir.join("part1_b.txt"), "a\n").unwrap();
+        std::fs::write(dir.join("part2_a.txt"), "a\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "not an example").unwrap();
+        std::fs::write(dir.join(ANSWERS_FILE), "part1_a: 2\npart1_b: 5\npart2_a:\n").unwrap();
+
+        let examples = load(&dir).unwrap();
+        assert_eq!(
+            examples.iter().map(|e| (e.name.as_str(), e.expected.as_deref())).collect::<Vec<_>>(),
+            [("part1_a", Some("2")), ("part1_b", Some("5")), ("part2_a", None)]
+        );
+        assert_eq!(load(&dir.join("missing")).unwrap(), vec![]);
+
+        // Only `part1_b` fails; part 2 is still `Todo`.
+        let problems: Vec<Box<dyn Problem>> = vec![Box::new(Lines)];
+        assert_eq!(check_all(&problems, src.to_str().unwrap(), None), 1);
+        assert_eq!(check_all(&problems, src.to_str().unwrap(), Some(4)), 0);
+
+        std::fs::remove_dir_all(&src).unwrap();
+    }
+
+    #[test]
+    fn test_answers_round_trip() {
+        let answers =
+            vec![("part1_a".to_owned(), Some("142".to_owned())), ("part2_a".to_owned(), None)];
+        let formatted = format_answers(&answers);
+        assert_eq!(formatted, "part1_a: 142\npart2_a:\n");
+        assert_eq!(parse_answers(&formatted), answers);
+    }
+}
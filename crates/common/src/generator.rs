@@ -0,0 +1,111 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::input_sets::quietly;
+
+/// A small seeded pseudo-random generator (SplitMix64). Generated inputs only
+/// need to be varied and reproducible from their seed, not unpredictable.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.below(hi - lo + 1)
+    }
+
+    /// An index into a collection of `len` items. `len` must not be zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+}
+
+/// Produces random inputs that follow the same rules as the real one, so
+/// solutions can be exercised beyond the single input we were given.
+pub trait InputGenerator {
+    /// Generates an input. `size` roughly scales the amount of work needed to
+    /// solve it, e.g. the number of instructions or directories.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    /// The largest `size` that still makes a bigger input, for days whose
+    /// inputs can't grow forever (e.g. numbers that must fit in a `u64`).
+    /// Larger sizes are clamped to it, so they aren't benchmarked.
+    fn max_size(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Checks `property` holds for `cases` generated inputs, with sizes cycling
+/// through `1..=max_size`. Panics with the seed, size and input of the first
+/// case that fails or panics, so it can be reproduced.
+pub fn check_property(
+    generator: &dyn InputGenerator,
+    cases: u64,
+    max_size: usize,
+    property: impl Fn(&str) -> bool,
+) {
+    for seed in 0..cases {
+        let size = seed as usize % max_size + 1;
+        let input = generator.generate(&mut Rng::new(seed), size);
+        let holds = quietly(|| catch_unwind(AssertUnwindSafe(|| property(&input))));
+        if !matches!(holds, Ok(true)) {
+            let outcome = if holds.is_ok() { "failed" } else { "panicked" };
+            panic!("Property {} for seed {} (size {}) on input:\n{}", outcome, seed, size, input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers;
+
+    impl InputGenerator for Numbers {
+        fn generate(&self, rng: &mut Rng, size: usize) -> String {
+            (0..size).map(|_| rng.between(1, 9).to_string()).collect::<Vec<_>>().join("\n")
+        }
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let values = (0..1000).map(|_| rng.between(3, 5)).collect::<Vec<_>>();
+        assert!(values.iter().all(|v| (3..=5).contains(v)));
+        assert!((3..=5).all(|v| values.contains(&v)));
+        assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn test_check_property() {
+        check_property(&Numbers, 50, 10, |input| input.lines().all(|l| l != "0"));
+
+        let failure =
+            catch_unwind(|| check_property(&Numbers, 50, 10, |input| input.lines().count() < 3))
+                .unwrap_err();
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("Property failed for seed 2 (size 3)"));
+    }
+}
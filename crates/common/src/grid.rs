@@ -0,0 +1,69 @@
+use std::slice::ChunksExact;
+
+/// A rectangular grid stored in row-major order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `f(row, col)` for every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..width * height).map(|i| f(i / width, i % width)).collect();
+        Self { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        if let Some(cell) = self.get_mut(row, col) {
+            *cell = value;
+        }
+    }
+
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn iter_row_col(&self) -> impl Iterator<Item = (&T, usize, usize)> {
+        self.cells.iter().enumerate().map(|(i, el)| (el, i / self.width, i % self.width))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self { width, height, cells: vec![value; width * height] }
+    }
+}
+
+impl<T: Default> Grid<T> {
+    /// Parses one row per non-empty line, mapping each character with `f`.
+    /// Short lines are padded with `T::default()`.
+    pub fn parse(input: &str, f: impl Fn(char) -> T) -> Self {
+        let lines = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&f));
+            cells.resize_with(len + width, T::default);
+        }
+        Self { width, height: lines.len(), cells }
+    }
+}
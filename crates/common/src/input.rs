@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use crate::crypt::{InputKey, KeyError};
+
+/// Environment variable that points at an alternative inputs directory.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+static INPUTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+static LOADED: OnceLock<Mutex<HashMap<(u16, String), &'static str>>> = OnceLock::new();
+
+/// Expands to a day's puzzle input, read from `<inputs>/<year>/<day>.txt`, or
+/// decrypted from `<inputs>/<year>/<day>.enc` if there's no plaintext copy.
+///
+/// With the `embed-inputs` feature enabled on the calling crate, the plaintext
+/// file is compiled into the binary from the workspace `inputs` directory (only
+/// the encrypted inputs are committed, so `make decrypt-inputs` first), and
+/// with `embed-encrypted-inputs` the encrypted file is compiled in and
+/// decrypted on first use. Otherwise it is loaded the first time it is needed,
+/// from the directory given to [`set_inputs_dir`], then `AOC_INPUTS_DIR`, then
+/// the workspace `inputs` directory.
+#[macro_export]
+macro_rules! problem_input {
+    ($year:literal, $day:ident) => {{
+        #[cfg(feature = "embed-inputs")]
+        let input: &'static str = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../inputs/",
+            $year,
+            "/",
+            stringify!($day),
+            ".txt"
+        ));
+        #[cfg(all(feature = "embed-encrypted-inputs", not(feature = "embed-inputs")))]
+        let input: &'static str = $crate::input::decrypt_embedded(
+            include_bytes!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../inputs/",
+                $year,
+                "/",
+                stringify!($day),
+                ".enc"
+            )),
+            $year,
+            stringify!($day),
+        );
+        #[cfg(not(any(feature = "embed-inputs", feature = "embed-encrypted-inputs")))]
+        let input: &'static str = $crate::input::load(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../inputs"),
+            $year,
+            stringify!($day),
+        );
+        input
+    }};
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Read(PathBuf, std::io::Error),
+    Key(PathBuf, KeyError),
+    Decrypt(PathBuf),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Read(path, e) => write!(
+                f,
+                "Could not read {:?} ({}). Fetch it with `make update-input`, or point {} at a \
+                 directory of inputs",
+                path, e, INPUTS_DIR_VAR
+            ),
+            InputError::Key(path, e) => write!(f, "{:?} is encrypted: {}", path, e),
+            InputError::Decrypt(path) => {
+                write!(f, "Could not decrypt {:?}, it was encrypted with a different key", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Overrides the inputs directory for the rest of the run. Returns false if it
+/// was already set.
+pub fn set_inputs_dir(dir: impl Into<PathBuf>) -> bool {
+    INPUTS_DIR.set(dir.into()).is_ok()
+}
+
+/// The directory inputs are read from, falling back to `default` when neither
+/// [`set_inputs_dir`] nor `AOC_INPUTS_DIR` say otherwise.
+pub fn inputs_dir(default: &str) -> PathBuf {
+    INPUTS_DIR
+        .get()
+        .cloned()
+        .or_else(|| std::env::var_os(INPUTS_DIR_VAR).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+pub fn input_path(inputs_dir: &Path, year: u16, day: &str) -> PathBuf {
+    inputs_dir.join(year.to_string()).join(format!("{}.txt", day))
+}
+
+pub fn encrypted_input_path(inputs_dir: &Path, year: u16, day: &str) -> PathBuf {
+    inputs_dir.join(year.to_string()).join(format!("{}.enc", day))
+}
+
+fn decrypt_file(path: &Path) -> Result<String, InputError> {
+    let data = std::fs::read(path).map_err(|e| InputError::Read(path.to_owned(), e))?;
+    let key = InputKey::load().map_err(|e| InputError::Key(path.to_owned(), e))?;
+    key.decrypt(&data).ok_or_else(|| InputError::Decrypt(path.to_owned()))
+}
+
+/// Reads an input from `inputs_dir` without caching it, decrypting it if
+/// there's only an encrypted copy.
+pub fn read_input(inputs_dir: &Path, year: u16, day: &str) -> Result<String, InputError> {
+    let plain_path = input_path(inputs_dir, year, day);
+    let encrypted_path = encrypted_input_path(inputs_dir, year, day);
+    if !plain_path.exists() && encrypted_path.exists() {
+        decrypt_file(&encrypted_path)
+    } else {
+        std::fs::read_to_string(&plain_path).map_err(|e| InputError::Read(plain_path, e))
+    }
+}
+
+/// Reads an input, caching it so repeated calls (e.g. while benchmarking)
+/// don't touch the filesystem again.
+pub fn try_load(default_dir: &str, year: u16, day: &str) -> Result<&'static str, InputError> {
+    let mut loaded = LOADED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(input) = loaded.get(&(year, day.to_owned())) {
+        return Ok(input);
+    }
+
+    let input = read_input(&inputs_dir(default_dir), year, day)?;
+    let input: &'static str = Box::leak(input.into_boxed_str());
+    loaded.insert((year, day.to_owned()), input);
+    Ok(input)
+}
+
+pub fn load(default_dir: &str, year: u16, day: &str) -> &'static str {
+    try_load(default_dir, year, day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Decrypts an input that was compiled into the binary.
+pub fn decrypt_embedded(data: &[u8], year: u16, day: &str) -> &'static str {
+    let mut loaded = LOADED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    loaded.entry((year, day.to_owned())).or_insert_with(|| {
+        let label = PathBuf::from(format!("<embedded {}/{}.enc>", year, day));
+        let key =
+            InputKey::load().unwrap_or_else(|e| panic!("{}", InputError::Key(label.clone(), e)));
+        let input = key.decrypt(data).unwrap_or_else(|| panic!("{}", InputError::Decrypt(label)));
+        Box::leak(input.into_boxed_str())
+    })
+}
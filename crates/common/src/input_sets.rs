@@ -0,0 +1,275 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::{Display, Formatter},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use crate::{
+    input::{inputs_dir, read_input},
+    Problem, Solution,
+};
+
+/// Directory inside the inputs directory with one inputs directory per extra
+/// account, laid out the same way as the main one.
+pub const ACCOUNTS_DIR: &str = "accounts";
+
+/// Name of the account whose inputs live directly in the inputs directory.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Every input fetched with one account's session token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputSet {
+    pub account: String,
+    pub dir: PathBuf,
+}
+
+/// Where the inputs for `account` are stored.
+pub fn account_dir(inputs_dir: &Path, account: &str) -> PathBuf {
+    if account == DEFAULT_ACCOUNT {
+        inputs_dir.to_owned()
+    } else {
+        inputs_dir.join(ACCOUNTS_DIR).join(account)
+    }
+}
+
+/// Lists the default input set followed by every account's, sorted by name.
+pub fn input_sets(inputs_dir: &Path) -> Vec<InputSet> {
+    let mut accounts = std::fs::read_dir(inputs_dir.join(ACCOUNTS_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    accounts.sort();
+
+    std::iter::once(DEFAULT_ACCOUNT.to_owned())
+        .chain(accounts)
+        .map(|account| InputSet { dir: account_dir(inputs_dir, &account), account })
+        .collect()
+}
+
+/// Accepted answers are stored next to the input, one part per line, with an
+/// empty line for a part that hasn't been solved.
+pub fn answers_path(inputs_dir: &Path, year: u16, day: &str) -> PathBuf {
+    inputs_dir.join(year.to_string()).join(format!("{}.answers", day))
+}
+
+pub fn read_answers(inputs_dir: &Path, year: u16, day: &str) -> [Option<String>; 2] {
+    let answers = std::fs::read_to_string(answers_path(inputs_dir, year, day)).unwrap_or_default();
+    let mut lines = answers.lines().map(|l| Some(l.trim().to_owned()).filter(|l| !l.is_empty()));
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Matches the answer accepted for this input.
+    Correct(Solution),
+    /// Produced an answer, but there's no accepted answer to compare with.
+    Unchecked(Solution),
+    Wrong {
+        expected: String,
+        actual: Solution,
+    },
+    Panicked(String),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Wrong { .. } | Outcome::Panicked(_) | Outcome::Unchecked(Solution::Error)
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct(solution) => write!(f, "{} (correct)", solution),
+            Outcome::Unchecked(solution) => write!(f, "{} (unchecked)", solution),
+            Outcome::Wrong { expected, actual } => {
+                write!(f, "{} (wrong, expected {})", actual, expected)
+            }
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<unknown>".to_owned())
+}
+
+pub(crate) fn solve(
+    solve: impl FnOnce() -> Solution,
+    expected: Option<&String>,
+) -> Option<Outcome> {
+    let outcome = match catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Solution::Todo) => return None,
+        Ok(actual) => match expected {
+            Some(expected) if *expected == actual.to_string() => Outcome::Correct(actual),
+            Some(expected) => Outcome::Wrong { expected: expected.clone(), actual },
+            None => Outcome::Unchecked(actual),
+        },
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    };
+    Some(outcome)
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without reporting panics on this thread. Solutions are expected to
+/// panic on inputs they can't handle, and a backtrace for each of them would
+/// drown out the report. The panic hook is process-wide, so it's replaced once
+/// with one that only stays silent for threads inside `quietly`, and panics on
+/// any other thread (e.g. other tests running in parallel) are still reported.
+pub(crate) fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.with(|quiet| quiet.set(self.0));
+        }
+    }
+
+    let _restore = Restore(QUIET.with(|quiet| quiet.replace(true)));
+    f()
+}
+
+/// Solves every problem against every stored input set, printing each result
+/// and a summary of the failures. Returns the number of failures.
+pub fn check_all(problems: &[Box<dyn Problem>], default_dir: &str, year: u16) -> usize {
+    let sets = input_sets(&inputs_dir(default_dir));
+    let mut failures = vec![];
+
+    quietly(|| {
+        for problem in problems {
+            let day = format!("day{:02}", problem.day());
+            for set in sets.iter() {
+                let Ok(input) = read_input(&set.dir, year, &day) else {
+                    continue;
+                };
+                let answers = read_answers(&set.dir, year, &day);
+                let parts = [
+                    solve(|| problem.solve_part1_with(&input), answers[0].as_ref()),
+                    solve(|| problem.solve_part2_with(&input), answers[1].as_ref()),
+                ];
+                for (part, outcome) in parts.into_iter().enumerate() {
+                    let Some(outcome) = outcome else {
+                        continue;
+                    };
+                    println!(
+                        "{} [{}] - Part {}: {}",
+                        problem.name(),
+                        set.account,
+                        part + 1,
+                        outcome
+                    );
+                    if outcome.is_failure() {
+                        failures.push(format!(
+                            "{} [{}] - Part {}: {}",
+                            problem.name(),
+                            set.account,
+                            part + 1,
+                            outcome
+                        ));
+                    }
+                }
+            }
+        }
+    });
+
+    if failures.is_empty() {
+        println!("\nAll solutions passed on {} input sets", sets.len());
+    } else {
+        println!("\n{} failures:", failures.len());
+        for failure in failures.iter() {
+            println!("  {}", failure);
+        }
+    }
+    failures.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Columns;
+
+    impl Problem for Columns {
+        fn problem_input(&self) -> &'static str {
+            ""
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "Columns"
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            let columns = [0u32; 3];
+            input.trim().parse::<usize>().map(|col| columns[col]).into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+    }
+
+    #[test]
+    fn test_check_all() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-sets-{}", std::process::id()));
+        let write = |account: &str, file: &str, contents: &str| {
+            let path = account_dir(&dir, account).join("2023");
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join(file), contents).unwrap();
+        };
+        write(DEFAULT_ACCOUNT, "day01.txt", "1\n");
+        write(DEFAULT_ACCOUNT, "day01.answers", "0\n\n");
+        write("alice", "day01.txt", "7\n");
+        write("bob", "day01.txt", "2\n");
+        write("bob", "day01.answers", "5\n");
+
+        let sets = input_sets(&dir);
+        assert_eq!(
+            sets.iter().map(|s| s.account.as_str()).collect::<Vec<_>>(),
+            ["default", "alice", "bob"]
+        );
+        assert_eq!(read_answers(&sets[0].dir, 2023, "day01"), [Some("0".to_owned()), None]);
+
+        let problems: Vec<Box<dyn Problem>> = vec![Box::new(Columns)];
+        assert_eq!(check_all(&problems, dir.to_str().unwrap(), 2023), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_quietly_is_per_thread() {
+        quietly(|| {
+            assert!(QUIET.with(Cell::get));
+            assert!(!std::thread::spawn(|| QUIET.with(Cell::get)).join().unwrap());
+        });
+        assert!(!QUIET.with(Cell::get));
+    }
+}
@@ -0,0 +1,281 @@
+/// A half-open range of values, `start..end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    pub const fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    pub const fn with_length(start: u64, length: u64) -> Self {
+        Self { start, end: start.saturating_add(length) }
+    }
+
+    pub const fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub const fn contains(&self, value: u64) -> bool {
+        value >= self.start && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+/// A set of values stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort_unstable();
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|i| i.len()).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.intervals.last().map(|i| i.end - 1)
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while let (Some(left), Some(right)) = (self.intervals.get(a), other.intervals.get(b)) {
+            if let Some(overlap) = left.intersect(right) {
+                result.push(overlap);
+            }
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::new();
+        let mut b = 0;
+        for interval in self.iter() {
+            let mut start = interval.start;
+            while let Some(cut) = other.intervals.get(b) {
+                if cut.end <= start {
+                    b += 1;
+                    continue;
+                }
+                if cut.start >= interval.end {
+                    break;
+                }
+                if cut.start > start {
+                    result.push(Interval::new(start, cut.start));
+                }
+                start = cut.end;
+                if start >= interval.end {
+                    break;
+                }
+                b += 1;
+            }
+            if start < interval.end {
+                result.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// Splits the set into the values below `at` and the values at or above
+    /// it.
+    pub fn split_at(&self, at: u64) -> (IntervalSet, IntervalSet) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for interval in self.iter() {
+            if interval.end <= at {
+                below.push(*interval);
+            } else if interval.start >= at {
+                above.push(*interval);
+            } else {
+                below.push(Interval::new(interval.start, at));
+                above.push(Interval::new(at, interval.end));
+            }
+        }
+        (IntervalSet { intervals: below }, IntervalSet { intervals: above })
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = IntervalSet { intervals: iter.into_iter().collect() };
+        set.normalize();
+        set
+    }
+}
+
+/// A mapping that shifts some source intervals to new destinations, leaving
+/// every other value unchanged. Where pieces overlap, the first one added
+/// wins.
+#[derive(Clone, Debug, Default)]
+pub struct PiecewiseMap {
+    pieces: Vec<(Interval, u64)>,
+}
+
+impl PiecewiseMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` onto the values starting at `dest`.
+    pub fn insert(&mut self, source: Interval, dest: u64) {
+        self.pieces.push((source, dest));
+    }
+
+    pub fn map(&self, value: u64) -> u64 {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(source, dest)| dest + (value - source.start))
+    }
+
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut unmapped = set.clone();
+        let mut mapped = Vec::new();
+        for (source, dest) in self.pieces.iter() {
+            let hit = unmapped.intersection(&IntervalSet::from(*source));
+            mapped.extend(
+                hit.iter().map(|i| Interval::with_length(dest + (i.start - source.start), i.len())),
+            );
+            unmapped = unmapped.difference(&hit);
+        }
+        mapped.extend(unmapped.intervals);
+        mapped.into_iter().collect()
+    }
+}
+
+/// A sequence of mappings applied one after the other.
+#[derive(Clone, Debug, Default)]
+pub struct MapChain {
+    maps: Vec<PiecewiseMap>,
+}
+
+impl MapChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, map: PiecewiseMap) {
+        self.maps.push(map);
+    }
+
+    pub fn map(&self, value: u64) -> u64 {
+        self.maps.iter().fold(value, |value, map| map.map(value))
+    }
+
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        self.maps.iter().fold(set.clone(), |set, map| map.map_set(&set))
+    }
+}
+
+impl FromIterator<PiecewiseMap> for MapChain {
+    fn from_iter<I: IntoIterator<Item = PiecewiseMap>>(iter: I) -> Self {
+        Self { maps: iter.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet {
+        intervals.iter().map(|&(s, e)| Interval::new(s, e)).collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (15, 20)]);
+        assert_eq!(a.union(&b), set(&[(0, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(3, 5), (10, 12)]));
+        assert_eq!(a.difference(&b), set(&[(0, 3), (12, 15)]));
+        assert_eq!(b.difference(&a), set(&[(5, 10), (15, 20)]));
+        assert_eq!(a.split_at(3), (set(&[(0, 3)]), set(&[(3, 5), (10, 15)])));
+        assert_eq!(a.len(), 10);
+        assert!(a.contains(14) && !a.contains(5));
+    }
+
+    #[test]
+    fn test_map_set() {
+        let mut map = PiecewiseMap::new();
+        map.insert(Interval::with_length(98, 2), 50);
+        map.insert(Interval::with_length(50, 48), 52);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(10), 10);
+        assert_eq!(map.map_set(&set(&[(45, 100)])), set(&[(45, 100)]));
+        assert_eq!(map.map_set(&set(&[(97, 100)])), set(&[(50, 52), (99, 100)]));
+
+        let chain: MapChain = [map.clone(), map].into_iter().collect();
+        assert_eq!(chain.map(98), 52);
+        assert_eq!(chain.map_set(&set(&[(98, 99)])), set(&[(52, 53)]));
+    }
+}
@@ -1,7 +1,21 @@
 mod bench_size;
 pub mod benchmark;
+pub mod crypt;
+pub mod cycle;
+pub mod differential;
+pub mod examples;
+pub mod generator;
+pub mod grid;
+pub mod input;
+pub mod input_sets;
+pub mod interval;
+pub mod math;
+pub mod ocr;
 pub mod problem;
+pub mod progress;
+pub mod scaling;
 pub mod solution;
+pub mod testing;
 
 pub use benchmark::{BenchmarkCollection, BenchmarkResult};
 pub use problem::Problem;
@@ -0,0 +1,236 @@
+/// Greatest common divisor of `a` and `b`.
+pub const fn gcd(a: u64, b: u64) -> u64 {
+    let mut a = a;
+    let mut b = b;
+    while b != 0 {
+        (b, a) = (a % b, b);
+    }
+    a
+}
+
+/// Least common multiple of `a` and `b`, or 0 if either is 0.
+pub const fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it doesn't fit in a
+/// `u64`.
+pub const fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of every value in the slice (0 for an empty slice).
+pub fn gcd_all(values: &[u64]) -> u64 {
+    values.iter().fold(0, |acc, &n| gcd(acc, n))
+}
+
+/// Least common multiple of every value in the slice (1 for an empty slice).
+pub fn lcm_all(values: &[u64]) -> u64 {
+    values.iter().fold(1, |acc, &n| lcm(acc, n))
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` such that `a * x + b * y
+/// == g`, where `g` is the (non-negative) greatest common divisor of `a` and
+/// `b`.
+pub const fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Multiplicative inverse of `a` modulo `modulus`, if one exists.
+pub const fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(modulus))
+}
+
+/// Computes `base ^ exp mod modulus` by repeated squaring.
+pub const fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let mut result = 1u128;
+    let mut base = base as u128 % modulus;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)`, given as
+/// `(residue, modulus)` pairs. The moduli don't need to be pairwise coprime.
+///
+/// Returns the combined `(residue, modulus)` with the residue in
+/// `0..modulus`, or `None` if the system has no solution.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let r = r.rem_euclid(m);
+        let (g, p, _) = extended_gcd(modulus, m);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+        // modulus * p ≡ g (mod m), so stepping by `modulus` a total of
+        // (r - residue) / g * p times lands on `r` modulo `m`.
+        let step = m / g;
+        // Both factors are below `step`, but the moduli can be large enough
+        // for their products to overflow even an `i128`.
+        let k = ((r - residue) / g % step).checked_mul(p % step)?.rem_euclid(step);
+        residue = residue.checked_add(modulus.checked_mul(k)?)?;
+        modulus = modulus.checked_mul(step)?;
+        residue = residue.rem_euclid(modulus);
+    }
+    Some((residue, modulus))
+}
+
+/// An event that first happens at step `offset` and then repeats every
+/// `period` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub offset: u64,
+    pub period: u64,
+}
+
+impl Cycle {
+    pub const fn new(offset: u64, period: u64) -> Self {
+        Self { offset, period }
+    }
+
+    /// Whether the event happens at step `step`.
+    pub const fn hits(&self, step: u64) -> bool {
+        if step < self.offset {
+            false
+        } else if self.period == 0 {
+            step == self.offset
+        } else {
+            (step - self.offset).is_multiple_of(self.period)
+        }
+    }
+
+    /// The cycle of steps where both this and `other` happen, if they ever do.
+    pub fn combine(&self, other: &Cycle) -> Option<Cycle> {
+        align_cycles(&[*self, *other])
+            .and_then(|offset| Some(Cycle::new(offset, checked_lcm(self.period, other.period)?)))
+    }
+}
+
+/// Finds the first step at which every cycle's event happens simultaneously,
+/// or `None` if they never line up.
+pub fn align_cycles(cycles: &[Cycle]) -> Option<u64> {
+    let earliest = cycles.iter().map(|c| c.offset).max().unwrap_or(0);
+    let mut congruences = Vec::with_capacity(cycles.len());
+    for cycle in cycles {
+        if cycle.period == 0 {
+            // A one-off event can only be matched exactly.
+            return cycles.iter().all(|c| c.hits(cycle.offset)).then_some(cycle.offset);
+        }
+        congruences.push((cycle.offset as i128, cycle.period as i128));
+    }
+
+    let (residue, modulus) = crt(&congruences)?;
+    let earliest = earliest as i128;
+    let step = if residue >= earliest {
+        residue
+    } else {
+        let cycles_behind = (earliest - residue + modulus - 1) / modulus;
+        residue.checked_add(cycles_behind.checked_mul(modulus)?)?
+    };
+    step.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(gcd_all(&[12, 18, 30]), 6);
+        assert_eq!(lcm_all(&[2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(&[]), 1);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn test_crt_large_moduli() {
+        // Primes just below 2^32, so the combined modulus is close to 2^64.
+        let congruences = [(1, 4_294_967_291), (2, 4_294_967_279), (3, 4_294_967_231)];
+        let (residue, modulus) = crt(&congruences).unwrap();
+        assert_eq!(modulus, 4_294_967_291 * 4_294_967_279 * 4_294_967_231);
+        for (r, m) in congruences {
+            assert_eq!(residue % m, r);
+        }
+
+        // Pairwise coprime moduli near 2^62 push the combined modulus past an
+        // `i128`.
+        let n = (1 << 62) + 1;
+        let big = [(1, n), (2, n + 1), (3, n + 2)];
+        assert_eq!(crt(&big), None);
+
+        let a = Cycle::new(1, 4_294_967_291);
+        let b = Cycle::new(2, 4_294_967_279);
+        let combined = a.combine(&b).unwrap();
+        assert!(a.hits(combined.offset) && b.hits(combined.offset));
+        assert_eq!(combined.period, 4_294_967_291 * 4_294_967_279);
+        assert_eq!(Cycle::new(0, u64::MAX).combine(&Cycle::new(0, u64::MAX - 1)), None);
+    }
+
+    #[test]
+    fn test_align_cycles() {
+        assert_eq!(align_cycles(&[Cycle::new(2, 2), Cycle::new(3, 3)]), Some(6));
+        assert_eq!(align_cycles(&[Cycle::new(5, 4), Cycle::new(3, 6)]), Some(9));
+        assert_eq!(align_cycles(&[Cycle::new(1, 4), Cycle::new(2, 6)]), None);
+        assert_eq!(align_cycles(&[Cycle::new(7, 0), Cycle::new(1, 3)]), Some(7));
+        assert_eq!(align_cycles(&[Cycle::new(7, 0), Cycle::new(1, 4)]), None);
+        assert_eq!(Cycle::new(2, 2).combine(&Cycle::new(3, 3)), Some(Cycle::new(6, 6)));
+    }
+}
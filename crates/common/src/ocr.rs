@@ -0,0 +1,279 @@
+use std::fmt::{Display, Formatter};
+
+use crate::grid::Grid;
+
+/// Glyphs are drawn with a fixed pitch, so the letter at index `n` always
+/// starts `n * pitch` columns after the first. Each glyph's rows are separated
+/// by spaces.
+struct Font {
+    height: usize,
+    pitch: usize,
+    glyphs: &'static [(char, &'static str)],
+}
+
+/// The 4x6 font, as seen in 2016 day 8, 2019 days 8 and 11, 2021 day 13 and
+/// 2022 day 10.
+static SMALL_FONT: Font = Font {
+    height: 6,
+    pitch: 5,
+    glyphs: &[
+        ('A', ".##. #..# #..# #### #..# #..#"),
+        ('B', "###. #..# ###. #..# #..# ###."),
+        ('C', ".##. #..# #... #... #..# .##."),
+        ('E', "#### #... ###. #... #... ####"),
+        ('F', "#### #... ###. #... #... #..."),
+        ('G', ".##. #..# #... #.## #..# .###"),
+        ('H', "#..# #..# #### #..# #..# #..#"),
+        ('I', ".### ..#. ..#. ..#. ..#. .###"),
+        ('J', "..## ...# ...# ...# #..# .##."),
+        ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+        ('L', "#... #... #... #... #... ####"),
+        ('O', ".##. #..# #..# #..# #..# .##."),
+        ('P', "###. #..# #..# ###. #... #..."),
+        ('R', "###. #..# #..# ###. #.#. #..#"),
+        ('S', ".### #... #... .##. ...# ###."),
+        ('U', "#..# #..# #..# #..# #..# .##."),
+        ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.."),
+        ('Z', "#### ...# ..#. .#.. #... ####"),
+    ],
+};
+
+/// The 6x10 font, as seen in 2018 day 10.
+static LARGE_FONT: Font = Font {
+    height: 10,
+    pitch: 8,
+    glyphs: &[
+        ('A', "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#"),
+        ('B', "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####."),
+        ('C', ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####."),
+        ('E', "###### #..... #..... #..... #####. #..... #..... #..... #..... ######"),
+        ('F', "###### #..... #..... #..... #####. #..... #..... #..... #..... #....."),
+        ('G', ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#"),
+        ('H', "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#"),
+        ('J', "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###.."),
+        ('K', "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#"),
+        ('L', "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######"),
+        ('N', "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#"),
+        ('P', "#####. #....# #....# #....# #####. #..... #..... #..... #..... #....."),
+        ('R', "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#"),
+        ('X', "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#"),
+        ('Z', "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######"),
+    ],
+};
+
+/// A block of pixels that didn't match any letter in the font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    /// Position of the glyph within the decoded text.
+    pub index: usize,
+    /// Leftmost column of the glyph within the image.
+    pub column: usize,
+    /// The glyph's pixels as `#`/`.` rows separated by newlines.
+    pub pixels: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The image (ignoring blank rows above and below) matches neither font.
+    UnsupportedHeight(usize),
+    /// Some glyphs couldn't be read; `text` holds the decoded text with a `?`
+    /// in place of each of them.
+    UnknownGlyphs { text: String, glyphs: Vec<UnknownGlyph> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "No letter font is {} pixels tall", height)
+            }
+            OcrError::UnknownGlyphs { text, glyphs } => {
+                write!(f, "Could not read every letter of {:?}", text)?;
+                for glyph in glyphs {
+                    write!(
+                        f,
+                        "\nUnknown glyph {} at column {}:\n{}",
+                        glyph.index, glyph.column, glyph.pixels
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the capital letters drawn in `image`, where `true` is a lit pixel.
+pub fn recognize(image: &Grid<bool>) -> Result<String, OcrError> {
+    let lit_rows = image
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| row.iter().any(|&p| p))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let (top, bottom) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom),
+        _ => return Ok(String::new()),
+    };
+    let height = bottom - top + 1;
+    let font = [&SMALL_FONT, &LARGE_FONT]
+        .into_iter()
+        .find(|f| f.height == height)
+        .ok_or(OcrError::UnsupportedHeight(height))?;
+
+    // Images can have blank columns on the left, or be trimmed right up to the
+    // first lit pixel, so glyphs are counted from the first lit column. Some
+    // letters (like `I`) start with a blank column themselves, which may have
+    // been trimmed off, so a few columns further left are tried too if that
+    // doesn't read cleanly.
+    let Some(left) = (0..image.width())
+        .find(|&col| (0..height).any(|row| *image.get(top + row, col).unwrap_or(&false)))
+    else {
+        return Ok(String::new());
+    };
+    let left = left as isize;
+    let mut first_attempt = None;
+    for start in (left - font.pitch as isize + 1..=left).rev() {
+        let (text, unknown) = read_glyphs(image, font, top, start);
+        if unknown.is_empty() {
+            return Ok(text);
+        }
+        first_attempt.get_or_insert((text, unknown));
+    }
+    let (text, glyphs) = first_attempt.unwrap_or_default();
+    Err(OcrError::UnknownGlyphs { text, glyphs })
+}
+
+/// Reads the glyphs of `font` in the rows from `top`, starting at column
+/// `start`, with a `?` for each glyph that isn't in the font. Columns left of
+/// the image are blank.
+fn read_glyphs(
+    image: &Grid<bool>,
+    font: &Font,
+    top: usize,
+    start: isize,
+) -> (String, Vec<UnknownGlyph>) {
+    let lit = |row: usize, col: isize| {
+        usize::try_from(col).is_ok_and(|col| *image.get(top + row, col).unwrap_or(&false))
+    };
+    let pitch = font.pitch as isize;
+    let glyph_count = (image.width() as isize - start).max(0).unsigned_abs().div_ceil(font.pitch);
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for index in 0..glyph_count {
+        let column = start + (index * font.pitch) as isize;
+        let cell = (0..font.height)
+            .map(|row| {
+                (column..column + pitch)
+                    .map(|col| if lit(row, col) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        if cell.iter().all(|row| !row.contains('#')) {
+            continue;
+        }
+
+        // Whatever is left of the cell to the right of the glyph has to be blank.
+        let found = font.glyphs.iter().find(|(_, glyph)| {
+            glyph.split(' ').zip(cell.iter()).all(|(glyph_row, row)| {
+                row.starts_with(glyph_row) && !row[glyph_row.len()..].contains('#')
+            })
+        });
+        match found {
+            Some((letter, _)) => text.push(*letter),
+            None => {
+                unknown.push(UnknownGlyph {
+                    index: text.chars().count(),
+                    column: column.max(0) as usize,
+                    pixels: cell.join("\n"),
+                });
+                text.push('?');
+            }
+        }
+    }
+    (text, unknown)
+}
+
+/// Reads the capital letters drawn in a string of `#` (lit) and `.` (unlit)
+/// rows.
+pub fn recognize_str(image: &str) -> Result<String, OcrError> {
+    recognize(&Grid::parse(image, |c| c == '#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_font() {
+        let image = "
+            #..#.###...##....##.####.#..#.#....#...#
+            #..#.#..#.#..#....#.#....#.#..#....#...#
+            ####.#..#.#..#....#.###..##...#.....#.#.
+            #..#.###..####....#.#....#.#..#......#..
+            #..#.#....#..#.#..#.#....#.#..#......#..
+            #..#.#....#..#..##..####.#..#.####...#..";
+        assert_eq!(recognize_str(image), Ok("HPAJEKLY".to_owned()));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let image = "
+            #....#..######..
+            #....#.......#..
+            .#..#........#..
+            .#..#.......#...
+            ..##.......#....
+            ..##......#.....
+            .#..#....#......
+            .#..#...#.......
+            #....#..#.......
+            #....#..######..";
+        assert_eq!(recognize_str(image), Ok("XZ".to_owned()));
+    }
+
+    #[test]
+    fn test_offset() {
+        // One blank column before the first letter.
+        let image = "
+            .#..#.###..
+            .#..#.#..#.
+            .####.#..#.
+            .#..#.###..
+            .#..#.#....
+            .#..#.#....";
+        assert_eq!(recognize_str(image), Ok("HP".to_owned()));
+
+        // Trimmed to the first lit pixel, which cuts off the blank column `I`
+        // starts with.
+        let image = "
+            ###.#..#
+            .#..#..#
+            .#..####
+            .#..#..#
+            .#..#..#
+            ###.#..#";
+        assert_eq!(recognize_str(image), Ok("IH".to_owned()));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let image = "
+            #..#.#...
+            #..#.#...
+            ####.#...
+            #..#.#...
+            #..#.#...
+            #..#.###.";
+        let Err(OcrError::UnknownGlyphs { text, glyphs }) = recognize_str(image) else {
+            panic!("Expected an unknown glyph");
+        };
+        assert_eq!(text, "H?");
+        assert_eq!(glyphs.len(), 1);
+        assert_eq!(glyphs[0].index, 1);
+        assert_eq!(glyphs[0].column, 5);
+        assert_eq!(recognize_str("#\n#\n#"), Err(OcrError::UnsupportedHeight(3)));
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::{Duration, Instant};
 
-use crate::{bench_size::MAX_BENCH_COUNT, BenchmarkResult, Solution};
+use crate::{bench_size::MAX_BENCH_COUNT, generator::InputGenerator, BenchmarkResult, Solution};
 
 macro_rules! measure {
     ($self:ident, $fn:ident) => {{
@@ -18,6 +18,41 @@ pub trait Problem {
     fn solve_part1_with(&self, input: &str) -> Solution;
     fn solve_part2_with(&self, input: &str) -> Solution;
 
+    /// Example inputs from the puzzle description, used to check variants of
+    /// the same day agree with each other.
+    fn examples(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Produces random valid inputs, for days that can be tested beyond their
+    /// real input and examples.
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        None
+    }
+
+    /// Whether copies of the real input joined with newlines still make a valid
+    /// input, letting days without a generator be benchmarked at larger sizes.
+    fn repeatable_input(&self) -> bool {
+        false
+    }
+
+    /// A brute force solution that is obviously correct, if slow, to check
+    /// the real one against on small generated inputs. Days without one keep
+    /// returning `Solution::Todo`.
+    fn reference_part1_with(&self, _input: &str) -> Solution {
+        Solution::Todo
+    }
+
+    fn reference_part2_with(&self, _input: &str) -> Solution {
+        Solution::Todo
+    }
+
+    /// Name of this alternative solution, if it isn't the day's primary one.
+    /// Reports group variants under the primary solution for the same day.
+    fn variant(&self) -> Option<&str> {
+        None
+    }
+
     fn solve_part1(&self) -> Solution {
         self.solve_part1_with(self.problem_input())
     }
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    input::{inputs_dir, read_input},
+    input_sets::{quietly, read_answers, solve, Outcome},
+    Problem,
+};
+
+/// Events ran for 25 days until 2024, and for 12 from 2025 on.
+pub const fn days_in_event(year: u32) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// How far along one part of a puzzle is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StarStatus {
+    /// Still returns `Solution::Todo`, or there's no input to run it on.
+    Todo,
+    /// Disagrees with the accepted answer, or panics.
+    Wrong,
+    /// Produces an answer, but there's no accepted answer to check it against.
+    Unverified,
+    /// Matches the answer the site accepted.
+    Done,
+}
+
+impl StarStatus {
+    fn from_outcome(outcome: Option<Outcome>) -> Self {
+        match outcome {
+            None => StarStatus::Todo,
+            Some(outcome) if outcome.is_failure() => StarStatus::Wrong,
+            Some(Outcome::Correct(_)) => StarStatus::Done,
+            Some(_) => StarStatus::Unverified,
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            StarStatus::Todo => '.',
+            StarStatus::Wrong => '!',
+            StarStatus::Unverified => '+',
+            StarStatus::Done => '*',
+        }
+    }
+
+    fn emoji(&self) -> &'static str {
+        match self {
+            StarStatus::Todo => " ",
+            StarStatus::Wrong => "❌",
+            StarStatus::Unverified => "❔",
+            StarStatus::Done => "⭐",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub name: String,
+    pub parts: [StarStatus; 2],
+}
+
+/// Star status for every registered day of a year. Variants of a day count
+/// towards the same stars, keeping whichever did best.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct YearProgress {
+    pub year: u16,
+    pub days: BTreeMap<u8, DayProgress>,
+}
+
+impl YearProgress {
+    pub fn new(year: u16) -> Self {
+        Self { year, days: BTreeMap::new() }
+    }
+
+    /// Records a solution's status. The day is named after its primary
+    /// solution, falling back to a variant's name until the primary is seen.
+    pub fn record(&mut self, day: u8, name: &str, variant: bool, parts: [StarStatus; 2]) {
+        let progress =
+            self.days.entry(day).or_insert_with(|| DayProgress { name: name.to_owned(), parts });
+        if !variant {
+            progress.name = name.to_owned();
+        }
+        for (best, status) in progress.parts.iter_mut().zip(parts) {
+            *best = (*best).max(status);
+        }
+    }
+
+    /// Solves every problem against the default inputs, checking the answers
+    /// against the accepted ones where they've been recorded.
+    pub fn collect(problems: &[Box<dyn Problem>], default_dir: &str, year: u16) -> Self {
+        let dir = inputs_dir(default_dir);
+        let mut progress = Self::new(year);
+
+        quietly(|| {
+            for problem in problems {
+                let day = format!("day{:02}", problem.day());
+                let parts = match read_input(&dir, year, &day) {
+                    Ok(input) => {
+                        let answers = read_answers(&dir, year, &day);
+                        [
+                            solve(|| problem.solve_part1_with(&input), answers[0].as_ref()),
+                            solve(|| problem.solve_part2_with(&input), answers[1].as_ref()),
+                        ]
+                        .map(StarStatus::from_outcome)
+                    }
+                    Err(_) => [StarStatus::Todo; 2],
+                };
+                progress.record(problem.day(), problem.name(), problem.variant().is_some(), parts);
+            }
+        });
+
+        progress
+    }
+
+    /// Stars that match an accepted answer.
+    pub fn stars(&self) -> usize {
+        self.days.values().flat_map(|d| d.parts).filter(|&p| p == StarStatus::Done).count()
+    }
+
+    /// Stars there are to collect this year, two for each day.
+    pub fn total_stars(&self) -> usize {
+        days_in_event(self.year.into()) as usize * 2
+    }
+
+    /// Renders the year in rows of five days, with each part shown as `*`
+    /// (done), `+` (unverified), `!` (wrong) or `.` (to do).
+    pub fn calendar(&self) -> String {
+        let mut out = format!(
+            "Advent of Code {} - {}/{} stars\n",
+            self.year,
+            self.stars(),
+            self.total_stars()
+        );
+        let days = (1..=days_in_event(self.year.into())).collect::<Vec<_>>();
+        for week in days.chunks(5) {
+            let cells = week
+                .iter()
+                .map(|day| {
+                    let parts = self.days.get(day).map_or([StarStatus::Todo; 2], |d| d.parts);
+                    format!("{:>2} {}{}", day, parts[0].symbol(), parts[1].symbol())
+                })
+                .collect::<Vec<_>>();
+            out.push_str(&cells.join("  "));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut buffer = String::from("## Progress\n\n");
+        buffer.push_str(&format!("{}/{} stars\n\n", self.stars(), self.total_stars()));
+        buffer.push_str("| Problem | Part 1 | Part 2 |\n");
+        buffer.push_str("| ------- | ------ | ------ |\n");
+        for progress in self.days.values() {
+            buffer.push_str(&format!(
+                "| {} | {} | {} |\n",
+                progress.name,
+                progress.parts[0].emoji(),
+                progress.parts[1].emoji()
+            ));
+        }
+        buffer
+    }
+}
+
+const SECTION_START: &str = "<!-- progress -->";
+const SECTION_END: &str = "<!-- /progress -->";
+
+/// Replaces the progress section of a README, or appends one if it doesn't
+/// have one yet. Everything else, like the benchmark results, is kept.
+pub fn replace_readme_section(readme: &str, section: &str) -> String {
+    let section = format!("{}\n{}{}", SECTION_START, section, SECTION_END);
+    match (readme.find(SECTION_START), readme.find(SECTION_END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &readme[..start], section, &readme[end + SECTION_END.len()..])
+        }
+        _ => format!("{}\n\n{}\n", readme.trim_end(), section),
+    }
+}
+
+pub fn write_readme(path: &Path, progress: &YearProgress) -> std::io::Result<()> {
+    let readme = std::fs::read_to_string(path).unwrap_or_default();
+    std::fs::write(path, replace_readme_section(&readme, &progress.to_markdown()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> YearProgress {
+        let mut progress = YearProgress::new(2023);
+        progress.record(1, "Day 1: Trebuchet?! (Alt)", true, [StarStatus::Wrong, StarStatus::Done]);
+        progress.record(1, "Day 1: Trebuchet?!", false, [StarStatus::Done, StarStatus::Unverified]);
+        progress.record(2, "Day 2: Cube Conundrum", false, [StarStatus::Wrong, StarStatus::Todo]);
+        progress
+    }
+
+    #[test]
+    fn test_calendar() {
+        let progress = sample();
+        assert_eq!(progress.stars(), 2);
+        let expected = [
+            "Advent of Code 2023 - 2/50 stars",
+            " 1 **   2 !.   3 ..   4 ..   5 ..",
+            " 6 ..   7 ..   8 ..   9 ..  10 ..",
+            "11 ..  12 ..  13 ..  14 ..  15 ..",
+            "16 ..  17 ..  18 ..  19 ..  20 ..",
+            "21 ..  22 ..  23 ..  24 ..  25 ..",
+        ];
+        assert_eq!(progress.calendar().lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_calendar_short_event() {
+        let mut progress = YearProgress::new(2025);
+        progress.record(12, "Day 12", false, [StarStatus::Done, StarStatus::Done]);
+        let expected = [
+            "Advent of Code 2025 - 2/24 stars",
+            " 1 ..   2 ..   3 ..   4 ..   5 ..",
+            " 6 ..   7 ..   8 ..   9 ..  10 ..",
+            "11 ..  12 **",
+        ];
+        assert_eq!(progress.calendar().lines().collect::<Vec<_>>(), expected);
+        assert!(progress.to_markdown().contains("2/24 stars"));
+    }
+
+    #[test]
+    fn test_readme_section() {
+        let section = sample().to_markdown();
+        assert!(section.contains("| Day 1: Trebuchet?! | ⭐ | ⭐ |"));
+
+        let readme = "# Advent of Code 2023\n\n## Results\n";
+        let updated = replace_readme_section(readme, &section);
+        assert!(updated.starts_with(readme));
+        assert!(updated.contains("<!-- progress -->\n## Progress"));
+        assert_eq!(replace_readme_section(&updated, &section), updated);
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    fmt::{Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use human_repr::HumanDuration;
+
+use crate::{
+    generator::{InputGenerator, Rng},
+    Problem, Solution,
+};
+
+/// Repeats the real input `size` times, for days where that's still valid.
+struct Repeated(&'static str);
+
+impl InputGenerator for Repeated {
+    fn generate(&self, _rng: &mut Rng, size: usize) -> String {
+        vec![self.0.trim_end(); size].join("\n")
+    }
+}
+
+/// Growth curves a timing can be fitted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    fn eval(&self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::Logarithmic => n.ln().max(1.0),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.ln().max(1.0),
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let notation = match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+        };
+        write!(f, "{}", notation)
+    }
+}
+
+/// The growth curve that best explains a set of timings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// Seconds per unit of `complexity`, i.e. `t ≈ scale * f(n)`.
+    pub scale: f64,
+    /// Slope of `log t` against `log n`, roughly the polynomial degree.
+    pub exponent: f64,
+}
+
+impl Fit {
+    /// Fits `points` of `(size, seconds)` to each curve in log space and keeps
+    /// the closest. Needs at least two points.
+    pub fn from_points(points: &[(f64, f64)]) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+
+        let logs = points.iter().map(|&(n, t)| (n.ln(), t.ln())).collect::<Vec<_>>();
+        let count = logs.len() as f64;
+        let mean_n = logs.iter().map(|p| p.0).sum::<f64>() / count;
+        let mean_t = logs.iter().map(|p| p.1).sum::<f64>() / count;
+        let covariance = logs.iter().map(|p| (p.0 - mean_n) * (p.1 - mean_t)).sum::<f64>();
+        let variance = logs.iter().map(|p| (p.0 - mean_n).powi(2)).sum::<f64>();
+        let exponent = if variance > 0.0 { covariance / variance } else { 0.0 };
+
+        Complexity::ALL
+            .iter()
+            .map(|&complexity| {
+                // The best scale in log space is the mean of the log ratios.
+                let ratios = points.iter().map(|&(n, t)| (t / complexity.eval(n)).ln());
+                let log_scale = ratios.sum::<f64>() / count;
+                let error = points
+                    .iter()
+                    .map(|&(n, t)| ((t / complexity.eval(n)).ln() - log_scale).powi(2))
+                    .sum::<f64>();
+                (Fit { complexity, scale: log_scale.exp(), exponent }, error)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(fit, _)| fit)
+    }
+
+    pub fn project(&self, size: f64) -> Duration {
+        Duration::from_secs_f64(self.scale * self.complexity.eval(size))
+    }
+
+    /// Scales a measured time at `from` up to `to` along the fitted curve,
+    /// which is closer than `project` when the fit is a little off.
+    pub fn extrapolate(&self, time: Duration, from: f64, to: f64) -> Duration {
+        time.mul_f64(self.complexity.eval(to) / self.complexity.eval(from))
+    }
+}
+
+pub struct ScalingResult {
+    pub name: String,
+    pub part: u8,
+    pub points: Vec<(usize, Duration)>,
+    pub fit: Option<Fit>,
+}
+
+impl Display for ScalingResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (Some(fit), Some(&(size, time))) = (self.fit, self.points.last()) else {
+            return write!(f, "{} - Part {}: not enough sizes to fit", self.name, self.part);
+        };
+        write!(
+            f,
+            "{} - Part {}: ~{} (exponent {:.2}), {} at size {}, ~{} projected at size {}",
+            self.name,
+            self.part,
+            fit.complexity,
+            fit.exponent,
+            time.human_duration(),
+            size,
+            fit.extrapolate(time, size as f64, size as f64 * 10.0).human_duration(),
+            size * 10
+        )
+    }
+}
+
+/// Sizes start here and double until a run takes longer than
+/// `MAX_RUN_TIME`, or `MAX_SIZE` (or the generator's own maximum) is reached.
+const MIN_SIZE: usize = 4;
+const MAX_SIZE: usize = 4096;
+const MAX_RUN_TIME: Duration = Duration::from_millis(200);
+/// Each size is repeated for about this long, keeping the fastest run.
+const TIME_PER_SIZE: Duration = Duration::from_millis(50);
+
+fn time(solve: impl Fn() -> Solution) -> Option<Duration> {
+    if solve() == Solution::Todo {
+        return None;
+    }
+    let started = Instant::now();
+    let mut fastest = Duration::MAX;
+    while started.elapsed() < TIME_PER_SIZE {
+        let start = Instant::now();
+        std::hint::black_box(solve());
+        fastest = fastest.min(start.elapsed());
+    }
+    Some(fastest)
+}
+
+fn scale_part(
+    problem: &dyn Problem,
+    generator: &dyn InputGenerator,
+    part: u8,
+) -> Option<ScalingResult> {
+    let max_size = generator.max_size().map_or(MAX_SIZE, |max| max.min(MAX_SIZE));
+    let mut points = vec![];
+    let mut size = MIN_SIZE;
+    while size <= max_size {
+        let input = generator.generate(&mut Rng::new(size as u64), size);
+        let elapsed = match part {
+            1 => time(|| problem.solve_part1_with(&input))?,
+            _ => time(|| problem.solve_part2_with(&input))?,
+        };
+        points.push((size, elapsed));
+        if elapsed > MAX_RUN_TIME {
+            break;
+        }
+        size *= 2;
+    }
+
+    let fit_points =
+        points.iter().map(|&(n, t)| (n as f64, t.as_secs_f64().max(1e-9))).collect::<Vec<_>>();
+    Some(ScalingResult {
+        name: problem.name().to_owned(),
+        part,
+        fit: Fit::from_points(&fit_points),
+        points,
+    })
+}
+
+/// Benchmarks both parts at growing input sizes and fits a growth curve to
+/// the timings. Days need a generator, or an input that can be repeated;
+/// `None` if there's neither.
+pub fn scale(problem: &dyn Problem) -> Option<Vec<ScalingResult>> {
+    let repeated = Repeated(problem.problem_input());
+    let generator = match problem.generator() {
+        Some(generator) => generator,
+        None if problem.repeatable_input() => &repeated,
+        None => return None,
+    };
+    Some([1, 2].into_iter().filter_map(|part| scale_part(problem, generator, part)).collect())
+}
+
+/// Prints the estimated complexity of every problem that can be scaled.
+pub fn scale_all(problems: &[Box<dyn Problem>]) {
+    for problem in problems {
+        match scale(problem.as_ref()) {
+            Some(results) => results.iter().for_each(|r| println!("{}", r)),
+            None => println!("{} - no generator or repeatable input", problem.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(f: impl Fn(f64) -> f64) -> Vec<(f64, f64)> {
+        [8.0, 16.0, 32.0, 64.0, 128.0, 256.0].iter().map(|&n| (n, f(n))).collect()
+    }
+
+    #[test]
+    fn test_fit() {
+        let fit = Fit::from_points(&points(|n| 3e-6 * n)).unwrap();
+        assert_eq!(fit.complexity, Complexity::Linear);
+        assert!((fit.exponent - 1.0).abs() < 1e-9);
+        assert!((fit.project(2560.0).as_secs_f64() - 7.68e-3).abs() < 1e-9);
+        let projected = fit.extrapolate(Duration::from_millis(2), 256.0, 2560.0);
+        assert_eq!(projected, Duration::from_millis(20));
+
+        let cases = [
+            (Complexity::Constant, points(|_| 2e-6)),
+            (Complexity::Linearithmic, points(|n| 1e-7 * n * n.ln())),
+            (Complexity::Quadratic, points(|n| 5e-8 * n * n)),
+            (Complexity::Cubic, points(|n| 1e-9 * n * n * n)),
+        ];
+        for (complexity, points) in cases {
+            assert_eq!(Fit::from_points(&points).unwrap().complexity, complexity);
+        }
+        assert_eq!(Fit::from_points(&[(8.0, 1.0)]), None);
+    }
+
+    #[test]
+    fn test_fit_noisy() {
+        let noise = [1.1, 0.9, 1.05, 0.97, 1.08, 0.95];
+        let noisy = points(|n| 4e-8 * n * n)
+            .into_iter()
+            .zip(noise)
+            .map(|((n, t), k)| (n, t * k))
+            .collect::<Vec<_>>();
+        let fit = Fit::from_points(&noisy).unwrap();
+        assert_eq!(fit.complexity, Complexity::Quadratic);
+        assert!((fit.exponent - 2.0).abs() < 0.1);
+    }
+
+    /// A day whose input stops growing at size 4.
+    struct Capped;
+
+    impl InputGenerator for Capped {
+        fn generate(&self, _rng: &mut Rng, size: usize) -> String {
+            "x\n".repeat(size.min(4))
+        }
+
+        fn max_size(&self) -> Option<usize> {
+            Some(4)
+        }
+    }
+
+    impl Problem for Capped {
+        fn problem_input(&self) -> &'static str {
+            ""
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "Capped"
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            input.lines().count().into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+
+        fn generator(&self) -> Option<&dyn InputGenerator> {
+            Some(self)
+        }
+    }
+
+    #[test]
+    fn test_scale_capped() {
+        let results = scale(&Capped).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].points.iter().map(|p| p.0).collect::<Vec<_>>(), [4]);
+        assert_eq!(results[0].to_string(), "Capped - Part 1: not enough sizes to fit");
+    }
+}
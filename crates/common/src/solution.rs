@@ -81,6 +81,12 @@ impl From<&str> for Solution {
     }
 }
 
+impl From<String> for Solution {
+    fn from(val: String) -> Self {
+        Solution::Str(val)
+    }
+}
+
 impl<T: Into<Solution>> From<Option<T>> for Solution {
     fn from(val: Option<T>) -> Self {
         match val {
@@ -0,0 +1,184 @@
+use crate::{differential::check_variants, Problem};
+
+/// Generates a day's test module from a compact spec: the expected answer for
+/// each part on the real input, optionally the examples with their answers,
+/// and optionally variants that should agree with the day. Any extra `#[test]`
+/// functions are added to the module as they are.
+///
+/// ```ignore
+/// common::aoc_test! {
+///     Day02 {};
+///     part1: Solution::U32(1606483), examples: ["2x3x4" => Solution::U32(58)];
+///     part2: Solution::U32(3842356), examples: ["2x3x4" => Solution::U32(34)];
+///     variants: [crate::day02fast::Day02Fast {}];
+/// }
+/// ```
+#[macro_export]
+macro_rules! aoc_test {
+    (
+        $problem:expr;
+        part1: $part1:expr $(, examples: [$($input1:expr => $expected1:expr),* $(,)?])?;
+        part2: $part2:expr $(, examples: [$($input2:expr => $expected2:expr),* $(,)?])?;
+        $(variants: [$($variant:expr),* $(,)?];)?
+        $(#[test] fn $name:ident() $body:block)*
+    ) => {
+        #[cfg(test)]
+        mod tests {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::Problem;
+
+            $(
+                #[test]
+                fn test_part1_example() {
+                    let problem = $problem;
+                    $(assert_eq!(problem.solve_part1_with($input1), $expected1);)*
+                }
+            )?
+
+            #[test]
+            fn test_part1_real_input() {
+                let problem = $problem;
+                assert_eq!(problem.solve_part1(), $part1);
+            }
+
+            $(
+                #[test]
+                fn test_part2_example() {
+                    let problem = $problem;
+                    $(assert_eq!(problem.solve_part2_with($input2), $expected2);)*
+                }
+            )?
+
+            #[test]
+            fn test_part2_real_input() {
+                let problem = $problem;
+                assert_eq!(problem.solve_part2(), $part2);
+            }
+
+            $(
+                #[test]
+                fn test_variants_agree() {
+                    $($crate::testing::assert_variants_agree(&$problem, &$variant);)*
+                }
+            )?
+
+            $(
+                #[test]
+                fn $name() $body
+            )*
+        }
+    };
+}
+
+/// Panics if `variant` gives a different answer to `problem` on the real input
+/// or any of the examples, showing the smallest input they disagree on.
+pub fn assert_variants_agree(problem: &dyn Problem, variant: &dyn Problem) {
+    let mut inputs = vec![("real input".to_owned(), problem.problem_input().to_owned())];
+    for (idx, example) in problem.examples().into_iter().enumerate() {
+        inputs.push((format!("example {}", idx + 1), example.to_owned()));
+    }
+    let disagreements = check_variants(&[problem, variant], &inputs);
+    if let Some(disagreement) = disagreements.first() {
+        panic!("{}", disagreement);
+    }
+}
+
+#[cfg(test)]
+mod fixtures {
+    pub use crate::{Problem, Solution};
+
+    /// Counts lines and bytes.
+    pub struct Count;
+
+    impl Problem for Count {
+        fn problem_input(&self) -> &'static str {
+            "a\nb\nc"
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "Count"
+        }
+
+        fn examples(&self) -> Vec<&'static str> {
+            vec!["a", "a\nb"]
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            input.lines().count().into()
+        }
+
+        fn solve_part2_with(&self, input: &str) -> Solution {
+            input.len().into()
+        }
+    }
+
+    /// Counts the same things a different way.
+    pub struct CountChars;
+
+    impl Problem for CountChars {
+        fn problem_input(&self) -> &'static str {
+            Count.problem_input()
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "Count (Chars)"
+        }
+
+        fn solve_part1_with(&self, input: &str) -> Solution {
+            (input.matches('\n').count() + 1).into()
+        }
+
+        fn solve_part2_with(&self, input: &str) -> Solution {
+            input.chars().count().into()
+        }
+    }
+
+    /// Always counts zero lines.
+    pub struct Empty;
+
+    impl Problem for Empty {
+        fn problem_input(&self) -> &'static str {
+            ""
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "Empty"
+        }
+
+        fn solve_part1_with(&self, _input: &str) -> Solution {
+            0usize.into()
+        }
+
+        fn solve_part2_with(&self, _input: &str) -> Solution {
+            Solution::Todo
+        }
+    }
+}
+
+#[cfg(test)]
+use fixtures::*;
+
+crate::aoc_test! {
+    Count;
+    part1: Solution::USize(3), examples: ["a" => Solution::USize(1), "a\nb" => Solution::USize(2)];
+    part2: Solution::USize(5);
+    variants: [CountChars];
+
+    #[test]
+    fn test_variants_disagree() {
+        assert!(std::panic::catch_unwind(|| assert_variants_agree(&Count, &Empty)).is_err());
+    }
+}
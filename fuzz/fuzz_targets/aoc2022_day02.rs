@@ -0,0 +1,16 @@
+#![no_main]
+
+use common::Problem;
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../crates/aoc2022/src/day02/mod.rs"]
+mod day02;
+
+fuzz_target!(|data: &[u8]| {
+    // Puzzle inputs are always text.
+    if let Ok(input) = std::str::from_utf8(data) {
+        let problem = day02::Day02 {};
+        let _ = problem.solve_part1_with(input);
+        let _ = problem.solve_part2_with(input);
+    }
+});